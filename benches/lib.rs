@@ -6,7 +6,7 @@ extern crate lifeguard;
 mod tests {
   use test::Bencher;
   use test::black_box;
-  use lifeguard::{Pool,RcRecycled};
+  use lifeguard::{Pool,RcRecycled,Recycleable};
 
   const ITERATIONS : u32 = 10_000;
 
@@ -113,4 +113,46 @@ mod tests {
           v1
       });
   }
+
+  struct Large {
+    words: [u64; 512]
+  }
+
+  impl Recycleable for Large {
+    fn new() -> Large {
+      Large { words: [0; 512] }
+    }
+    fn reset(&mut self) {}
+  }
+
+  struct Holder {
+    large: Option<Large>
+  }
+
+  #[bench]
+  fn bench07_detach_and_assign(b: &mut Bencher) {
+    let pool : Pool<Large> = Pool::with_size(1);
+    let mut holder = Holder { large: None };
+    b.iter(|| {
+      for _ in 0..ITERATIONS {
+        let large = pool.new().detach();
+        holder.large = Some(large);
+        black_box(holder.large.as_ref().map(|large| large.words[0]));
+        pool.attach(holder.large.take().unwrap());
+      }
+    });
+  }
+
+  #[bench]
+  fn bench08_detach_into(b: &mut Bencher) {
+    let pool : Pool<Large> = Pool::with_size(1);
+    let mut holder = Holder { large: None };
+    b.iter(|| {
+      for _ in 0..ITERATIONS {
+        pool.new().detach_into(&mut holder.large);
+        black_box(holder.large.as_ref().map(|large| large.words[0]));
+        pool.attach(holder.large.take().unwrap());
+      }
+    });
+  }
 }
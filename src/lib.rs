@@ -1,20 +1,124 @@
 #![allow(dead_code)]
-use std::rc::Rc;
-use std::cell::RefCell;
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+#[cfg(feature = "bytes")]
+extern crate bytes;
+#[cfg(feature = "log")]
+#[macro_use]
+extern crate log;
+#[cfg(feature = "metrics")]
+extern crate metrics;
+
+#[cfg(any(feature = "log", feature = "metrics"))]
+use std::any::type_name;
+use std::any::Any;
+use std::rc::{Rc, Weak};
+#[cfg(feature = "allocator_api")]
+use std::alloc::Allocator;
+use std::alloc::{self, Layout};
+use std::cmp;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+use std::mem;
+use std::str;
+use std::sync::{Mutex, MutexGuard};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::cell::{Cell, Ref, RefCell, RefMut};
+use std::error;
 use std::fmt;
 use std::ops::{Drop, Deref, DerefMut};
+use std::pin::Pin;
+use std::ptr::{self, NonNull};
+use std::slice;
 use std::convert::{AsRef, AsMut};
-use std::borrow::Borrow;
+use std::convert::TryInto;
+use std::io;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "log")]
+macro_rules! pool_log {
+  ($level: ident, $($arg: tt)+) => {
+    $level!(target: "lifeguard::Pool", $($arg)+)
+  }
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! pool_log {
+  ($level: ident, $($arg: tt)+) => {}
+}
+
+#[cfg(feature = "metrics")]
+macro_rules! pool_metric {
+  ($kind: ident, $name: expr, $pool: expr, $op: ident, $value: expr) => {
+    metrics::$kind!($name, "pool" => $pool).$op($value)
+  }
+}
+
+#[cfg(not(feature = "metrics"))]
+macro_rules! pool_metric {
+  ($kind: ident, $($arg: tt)+) => {}
+}
 
 pub trait Recycleable {
   fn new() -> Self;
   fn reset(&mut self);
+  #[inline] 
+  fn is_reset(&self) -> bool {
+    true
+  }
+  #[inline] 
+  fn capacity(&self) -> usize {
+    0
+  }
+}
+
+pub trait TryRecycleable: Recycleable + Sized {
+  type Error;
+  fn try_new() -> Result<Self, Self::Error>;
+}
+
+/// The counterpart of `Recycleable` for types that must stay pinned.
+///
+/// `reset` only receives a `Pin<&mut Self>`, so it has to restore the value
+/// in place without moving it. A `Pin<Box<T>>` of such a type is
+/// `Recycleable` and can be pooled.
+pub trait PinnedRecycleable {
+  fn new() -> Self;
+  fn reset(self: Pin<&mut Self>);
 }
 
 pub trait InitializeWith<T> {
   fn initialize_with(&mut self, source: T);
 }
 
+/// A pool whose value type is erased, so pools of different types can be
+/// stored together, e.g. as `Box<dyn AnyPool>` values in a map.
+pub trait AnyPool {
+  /// Checks out a value as a `Box<dyn Any>`.
+  ///
+  /// For a `Pool<T>` the box holds an `RcRecycled<T>`, which must be
+  /// recovered with `downcast::<RcRecycled<T>>()` before the value can be
+  /// used. Dropping the box, downcast or not, returns the value to its pool.
+  fn checkout_any(&self) -> Box<dyn Any>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+  MissingValue
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      Error::MissingValue => write!(f, "Recycled<T> smartpointer missing its value.")
+    }
+  }
+}
+
+impl error::Error for Error {}
+
 impl Recycleable for String {
   #[inline] 
   fn new() -> String {
@@ -24,8 +128,17 @@ impl Recycleable for String {
   fn reset(&mut self) {
     self.clear();
   }
+  #[inline] 
+  fn is_reset(&self) -> bool {
+    self.is_empty()
+  }
+  #[inline] 
+  fn capacity(&self) -> usize {
+    self.capacity()
+  }
 }
 
+#[cfg(not(feature = "allocator_api"))]
 impl <T> Recycleable for Vec<T> {
   #[inline] 
   fn new() -> Vec<T> {
@@ -35,6 +148,64 @@ impl <T> Recycleable for Vec<T> {
   fn reset(&mut self) {
     self.clear();
   }
+  #[inline] 
+  fn is_reset(&self) -> bool {
+    self.is_empty()
+  }
+  #[inline] 
+  fn capacity(&self) -> usize {
+    self.capacity()
+  }
+}
+
+#[cfg(feature = "allocator_api")]
+impl <T, A> Recycleable for Vec<T, A> where A: Allocator + Default {
+  #[inline] 
+  fn new() -> Vec<T, A> {
+    Vec::new_in(A::default())
+  }
+  #[inline] 
+  fn reset(&mut self) {
+    self.clear();
+  }
+  #[inline] 
+  fn is_reset(&self) -> bool {
+    self.is_empty()
+  }
+  #[inline] 
+  fn capacity(&self) -> usize {
+    self.capacity()
+  }
+}
+
+impl <K, V, S> Recycleable for HashMap<K, V, S> where K: Eq + Hash, S: BuildHasher + Default {
+  #[inline] 
+  fn new() -> HashMap<K, V, S> {
+    HashMap::with_hasher(S::default())
+  }
+  #[inline] 
+  fn reset(&mut self) {
+    self.clear();
+  }
+  #[inline] 
+  fn is_reset(&self) -> bool {
+    self.is_empty()
+  }
+  #[inline] 
+  fn capacity(&self) -> usize {
+    self.capacity()
+  }
+}
+
+impl <T> Recycleable for Pin<Box<T>> where T: PinnedRecycleable {
+  #[inline] 
+  fn new() -> Pin<Box<T>> {
+    Box::pin(T::new())
+  }
+  #[inline] 
+  fn reset(&mut self) {
+    PinnedRecycleable::reset(self.as_mut());
+  }
 }
 
 impl <A> InitializeWith<A> for String where A : AsRef<str> {
@@ -45,233 +216,1875 @@ impl <A> InitializeWith<A> for String where A : AsRef<str> {
   }
 }
 
-pub struct RcRecycled<T> where T: Recycleable {
-  value: RecycledInner<Rc<RefCell<Vec<T>>>, T>
-}
-
-pub struct Recycled<'a, T: 'a> where T: Recycleable {
-  value: RecycledInner<&'a RefCell<Vec<T>>, T>
+pub struct Buffer<const N: usize> {
+  bytes: Box<[u8; N]>,
+  len: usize
 }
 
-macro_rules! impl_recycled {
-  ($name: ident, $typ: ty, $pool: ty) => {
-  impl <'a, T> AsRef<T> for $typ where T : Recycleable {
-     fn as_ref(&self) -> &T {
-      self.value.as_ref()
-    }
+impl <const N: usize> Buffer<N> {
+  #[inline] 
+  pub fn len(&self) -> usize {
+    self.len
   }
 
-  impl <'a, T> AsMut<T> for $typ where T : Recycleable {
-     fn as_mut(&mut self) -> &mut T {
-      self.value.as_mut()
-    }
+  #[inline] 
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
   }
 
-  impl <'a, T> fmt::Debug for $typ where T : fmt::Debug + Recycleable {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-      self.value.fmt(f)
-    }
+  #[inline] 
+  pub fn capacity(&self) -> usize {
+    N
   }
 
-  impl <'a, T> fmt::Display for $typ where T : fmt::Display + Recycleable {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-      self.value.fmt(f)
-    }
+  #[inline] 
+  pub fn clear(&mut self) {
+    self.len = 0;
   }
 
-  impl <'a, T> Deref for $typ where T : Recycleable {
-    type Target = T;
-    #[inline] 
-    fn deref(&self) -> &T {
-      self.as_ref()
-    }
+  #[inline] 
+  pub fn set_len(&mut self, len: usize) {
+    assert!(len <= N, "Buffer<{}> cannot have a length of {}.", N, len);
+    self.len = len;
   }
 
-  impl <'a, T> DerefMut for $typ where T : Recycleable {
-    #[inline] 
-    fn deref_mut(&mut self) -> &mut T {
-      self.as_mut()
-    }
+  #[inline] 
+  pub fn as_slice(&self) -> &[u8] {
+    &self.bytes[..self.len]
   }
 
-  impl <'a, T> $typ where T: Recycleable {
-    pub fn new(pool: $pool, value: T) -> $typ {
-      $name { value: RecycledInner::new(pool, value) }
-    }
-    
-    #[inline] 
-    pub fn new_from<A>(pool: $pool, value: T, source: A) -> $typ where T : InitializeWith<A> {
-      $name { value: RecycledInner::new_from(pool, value, source) }
-    }
+  #[inline] 
+  pub fn as_mut_slice(&mut self) -> &mut [u8] {
+    &mut self.bytes[..self.len]
+  }
 
-    #[inline] 
-    pub fn detach(self) -> T {
-      self.value.detach()
-    }
+  #[inline] 
+  pub fn spare_capacity_mut(&mut self) -> &mut [u8] {
+    &mut self.bytes[self.len..]
   }
 }
-}
-impl_recycled!{ RcRecycled, RcRecycled<T>, Rc<RefCell<Vec<T>>> }
-impl_recycled!{ Recycled, Recycled<'a, T>, &'a RefCell<Vec<T>> }
 
-struct RecycledInner<P, T> where P: Borrow<RefCell<Vec<T>>>, T : Recycleable {
-  value: Option<T>,
-  pool: P
+impl <const N: usize> Recycleable for Buffer<N> {
+  #[inline] 
+  fn new() -> Buffer<N> {
+    // Building the array on the heap directly avoids a large stack temporary.
+    let bytes: Box<[u8; N]> = match vec![0u8; N].into_boxed_slice().try_into() {
+      Ok(bytes) => bytes,
+      Err(_) => unreachable!()
+    };
+    Buffer { bytes, len: 0 }
+  }
+  #[inline] 
+  fn reset(&mut self) {
+    self.len = 0;
+  }
+  #[inline] 
+  fn is_reset(&self) -> bool {
+    self.len == 0
+  }
+  #[inline] 
+  fn capacity(&self) -> usize {
+    N
+  }
 }
 
-impl <P, T> Drop for RecycledInner<P, T> where P: Borrow<RefCell<Vec<T>>>, T : Recycleable {
+impl <const N: usize> Deref for Buffer<N> {
+  type Target = [u8];
   #[inline] 
-  fn drop(&mut self) {
-    if let Some(mut value) = self.value.take() {
-      value.reset();
-      self.pool.borrow().borrow_mut().push(value);
-    }
+  fn deref(&self) -> &[u8] {
+    self.as_slice()
   }
 }
 
-impl <P, T> AsRef<T> for RecycledInner<P, T> where P: Borrow<RefCell<Vec<T>>>, T : Recycleable {
-   fn as_ref(&self) -> &T {
-    match self.value.as_ref() {
-      Some(v) => v,
-      None => panic!("Recycled<T> smartpointer missing its value.")
-    }
+impl <const N: usize> DerefMut for Buffer<N> {
+  #[inline] 
+  fn deref_mut(&mut self) -> &mut [u8] {
+    self.as_mut_slice()
   }
 }
 
-impl <P, T> AsMut<T> for RecycledInner<P, T> where P: Borrow<RefCell<Vec<T>>>, T : Recycleable {
-   fn as_mut(&mut self) -> &mut T {
-    match self.value.as_mut() {
-      Some(v) => v,
-      None => panic!("Recycled<T> smartpointer missing its value.")
-    }
+impl <const N: usize> AsRef<[u8]> for Buffer<N> {
+  fn as_ref(&self) -> &[u8] {
+    self.as_slice()
   }
 }
 
-impl <P, T> fmt::Debug for RecycledInner<P, T> where P: Borrow<RefCell<Vec<T>>>, T : fmt::Debug + Recycleable {
-  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    match self.value {
-      Some(ref s) => s.fmt(f),
-      None => write!(f, "Empty Recycled<T>")
-    }
+impl <const N: usize> AsMut<[u8]> for Buffer<N> {
+  fn as_mut(&mut self) -> &mut [u8] {
+    self.as_mut_slice()
   }
 }
 
-impl <P, T> fmt::Display for RecycledInner<P, T> where P: Borrow<RefCell<Vec<T>>>, T : fmt::Display + Recycleable {
+impl <const N: usize> fmt::Debug for Buffer<N> {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    match self.value {
-      Some(ref s) => s.fmt(f),
-      None => write!(f, "Empty Recycled<T>")
-    }
+    f.debug_struct("Buffer")
+      .field("len", &self.len)
+      .field("capacity", &N)
+      .finish()
   }
 }
 
-impl <P, T> Deref for RecycledInner<P, T> where P: Borrow<RefCell<Vec<T>>>, T : Recycleable {
-  type Target = T;
+impl <const N: usize> io::Write for Buffer<N> {
   #[inline] 
-  fn deref<'a>(&'a self) -> &'a T {
-    self.as_ref()
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    let count = cmp::min(buf.len(), N - self.len);
+    self.bytes[self.len..self.len + count].copy_from_slice(&buf[..count]);
+    self.len += count;
+    Ok(count)
   }
-}
 
-impl <P, T> DerefMut for RecycledInner<P, T> where P: Borrow<RefCell<Vec<T>>>, T : Recycleable {
   #[inline] 
-  fn deref_mut<'a>(&'a mut self) -> &'a mut T {
-    self.as_mut()
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
   }
 }
 
-impl <P, T> RecycledInner<P, T> where P: Borrow<RefCell<Vec<T>>>, T : Recycleable {
+/// A growable byte buffer whose storage is aligned to `ALIGN` bytes, which
+/// must be a power of two.
+///
+/// Resetting the buffer only sets its length to zero, so a pooled buffer
+/// keeps its aligned allocation between checkouts. The whole allocation is
+/// zeroed when it is made, so `set_len` and `spare_capacity_mut` never
+/// expose uninitialized memory.
+pub struct AlignedBuffer<const ALIGN: usize> {
+  ptr: NonNull<u8>,
+  len: usize,
+  capacity: usize
+}
+
+// The buffer owns its allocation exclusively, like a `Vec<u8>`.
+unsafe impl <const ALIGN: usize> Send for AlignedBuffer<ALIGN> {}
+unsafe impl <const ALIGN: usize> Sync for AlignedBuffer<ALIGN> {}
+
+impl <const ALIGN: usize> AlignedBuffer<ALIGN> {
+  const ALIGN_IS_POWER_OF_TWO: () = assert!(ALIGN.is_power_of_two(), "AlignedBuffer alignment must be a power of two.");
+
   #[inline] 
-  fn new(pool: P, value: T) -> RecycledInner<P, T> {
-    RecycledInner {
-      value: Some(value),
-      pool: pool
-    }
+  pub fn with_capacity(capacity: usize) -> AlignedBuffer<ALIGN> {
+    let mut buffer: AlignedBuffer<ALIGN> = Recycleable::new();
+    buffer.reserve(capacity);
+    buffer
   }
-  
+
   #[inline] 
-  fn new_from<A>(pool: P, mut value: T, source: A) -> RecycledInner<P, T> where T : InitializeWith<A> {
-    value.initialize_with(source);
-    RecycledInner {
-      value: Some(value),
-      pool: pool
+  fn layout(capacity: usize) -> Layout {
+    match Layout::from_size_align(capacity, ALIGN) {
+      Ok(layout) => layout,
+      Err(_) => panic!("AlignedBuffer<{}> cannot have a capacity of {}.", ALIGN, capacity)
     }
   }
 
   #[inline] 
-  fn detach(mut self) -> T {
-    let value = self.value.take().unwrap();
-    drop(self);
-    value
+  pub fn len(&self) -> usize {
+    self.len
   }
-}
 
-pub struct Pool <T> where T : Recycleable {
-  values: Rc<RefCell<Vec<T>>>
-}
+  #[inline] 
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
 
-impl <T> Pool <T>
-  where T: Recycleable {
+  #[inline] 
+  pub fn capacity(&self) -> usize {
+    self.capacity
+  }
 
-  #[inline]
-  pub fn with_size(size: u32) -> Pool <T> {
-    let values: Vec<T> = 
-      (0..size)
-      .map(|_| T::new() )
-      .collect();
-    Pool {
-      values: Rc::new(RefCell::new(values))
+  #[inline] 
+  pub fn clear(&mut self) {
+    self.len = 0;
+  }
+
+  #[inline] 
+  pub fn set_len(&mut self, len: usize) {
+    assert!(len <= self.capacity, "AlignedBuffer<{}> with a capacity of {} cannot have a length of {}.", ALIGN, self.capacity, len);
+    self.len = len;
+  }
+
+  pub fn reserve(&mut self, additional: usize) {
+    let required = match self.len.checked_add(additional) {
+      Some(required) => required,
+      None => panic!("AlignedBuffer<{}> capacity overflow.", ALIGN)
+    };
+    if required <= self.capacity {
+      return;
     }
+    let capacity = cmp::max(required, self.capacity.saturating_mul(2));
+    let layout = AlignedBuffer::<ALIGN>::layout(capacity);
+    let ptr = match NonNull::new(unsafe { alloc::alloc_zeroed(layout) }) {
+      Some(ptr) => ptr,
+      None => alloc::handle_alloc_error(layout)
+    };
+    unsafe {
+      ptr::copy_nonoverlapping(self.ptr.as_ptr(), ptr.as_ptr(), self.len);
+    }
+    self.deallocate();
+    self.ptr = ptr;
+    self.capacity = capacity;
   }
 
   #[inline] 
-  pub fn attach_rc(&self, value: T) -> RcRecycled<T> {
-    let pool_reference = self.values.clone();
-    RcRecycled { value: RecycledInner::new(pool_reference, value) }
+  fn deallocate(&mut self) {
+    if self.capacity != 0 {
+      unsafe { alloc::dealloc(self.ptr.as_ptr(), AlignedBuffer::<ALIGN>::layout(self.capacity)) }
+    }
   }
 
   #[inline] 
-  pub fn new_rc(&self) -> RcRecycled<T> {
-    let t = self.detached();
-    let pool_reference = self.values.clone();
-    RcRecycled { value: RecycledInner::new(pool_reference, t) }
+  pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+    self.reserve(bytes.len());
+    let len = self.len;
+    self.spare_capacity_mut()[..bytes.len()].copy_from_slice(bytes);
+    self.len = len + bytes.len();
   }
- 
-  #[inline(always)] 
-  pub fn new_rc_from<A>(&self, source: A) -> RcRecycled<T> where T: InitializeWith<A> {
-    let t = self.detached();
-    let pool_reference = self.values.clone();
-    RcRecycled { value: RecycledInner::new_from(pool_reference, t, source) }
+
+  #[inline] 
+  pub fn as_ptr(&self) -> *const u8 {
+    self.ptr.as_ptr()
   }
 
   #[inline] 
-  pub fn attach(&self, value: T) -> Recycled<T> {
-    Recycled { value: RecycledInner::new(&*self.values, value) }
+  pub fn as_slice(&self) -> &[u8] {
+    unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
   }
 
   #[inline] 
-  pub fn new(&self) -> Recycled<T> {
-    let t = self.detached();
-    Recycled { value: RecycledInner::new(&*self.values, t) }
+  pub fn as_mut_slice(&mut self) -> &mut [u8] {
+    unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
   }
 
-  #[inline(always)] 
-  pub fn new_from<A>(&self, source: A) -> Recycled<T> where T: InitializeWith<A> {
-    let t = self.detached();
-    Recycled { value: RecycledInner::new_from(&*self.values, t, source) }
+  #[inline] 
+  pub fn spare_capacity_mut(&mut self) -> &mut [u8] {
+    unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr().add(self.len), self.capacity - self.len) }
   }
+}
 
+impl <const ALIGN: usize> Recycleable for AlignedBuffer<ALIGN> {
   #[inline] 
-  pub fn detached(&self) -> T {
-    match self.values.borrow_mut().pop() {
-      Some(v) => v,
-      None => T::new()
-    }
+  fn new() -> AlignedBuffer<ALIGN> {
+    let () = AlignedBuffer::<ALIGN>::ALIGN_IS_POWER_OF_TWO;
+    // An empty buffer has no allocation, only a suitably aligned pointer.
+    let ptr = match NonNull::new(ptr::without_provenance_mut(ALIGN)) {
+      Some(ptr) => ptr,
+      None => unreachable!()
+    };
+    AlignedBuffer { ptr, len: 0, capacity: 0 }
+  }
+  #[inline] 
+  fn reset(&mut self) {
+    self.len = 0;
+  }
+  #[inline] 
+  fn is_reset(&self) -> bool {
+    self.len == 0
   }
+  #[inline] 
+  fn capacity(&self) -> usize {
+    self.capacity
+  }
+}
 
+impl <const ALIGN: usize> Drop for AlignedBuffer<ALIGN> {
   #[inline] 
-  pub fn size(&self) -> usize {
-    (*self.values).borrow().len()
+  fn drop(&mut self) {
+    self.deallocate();
+  }
+}
+
+impl <const ALIGN: usize> Deref for AlignedBuffer<ALIGN> {
+  type Target = [u8];
+  #[inline] 
+  fn deref(&self) -> &[u8] {
+    self.as_slice()
+  }
+}
+
+impl <const ALIGN: usize> DerefMut for AlignedBuffer<ALIGN> {
+  #[inline] 
+  fn deref_mut(&mut self) -> &mut [u8] {
+    self.as_mut_slice()
+  }
+}
+
+impl <const ALIGN: usize> AsRef<[u8]> for AlignedBuffer<ALIGN> {
+  fn as_ref(&self) -> &[u8] {
+    self.as_slice()
+  }
+}
+
+impl <const ALIGN: usize> AsMut<[u8]> for AlignedBuffer<ALIGN> {
+  fn as_mut(&mut self) -> &mut [u8] {
+    self.as_mut_slice()
+  }
+}
+
+impl <const ALIGN: usize> fmt::Debug for AlignedBuffer<ALIGN> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("AlignedBuffer")
+      .field("len", &self.len)
+      .field("capacity", &self.capacity)
+      .field("align", &ALIGN)
+      .finish()
+  }
+}
+
+impl <const ALIGN: usize> io::Write for AlignedBuffer<ALIGN> {
+  #[inline] 
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    self.extend_from_slice(buf);
+    Ok(buf.len())
+  }
+
+  #[inline] 
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
+
+pub struct RcRecycled<T> where T: Recycleable {
+  value: RecycledInner<Rc<RefCell<CappedCollection<T>>>, T>
+}
+
+pub struct Recycled<'a, T: 'a> where T: Recycleable {
+  value: RecycledInner<&'a RefCell<CappedCollection<T>>, T>
+}
+
+pub type SharedRecycled<T> = Rc<RcRecycled<T>>;
+
+pub struct WeakRecycled<T> where T: Recycleable {
+  value: RecycledInner<Weak<RefCell<CappedCollection<T>>>, T>
+}
+
+/// A guard for a value checked out of a `SyncPool`.
+///
+/// The guard borrows its pool, so it is `Send` when `T: Send` but can only
+/// be moved to threads that cannot outlive the pool, such as those spawned
+/// with `std::thread::scope`.
+pub struct SyncRecycled<'a, T: 'a> where T: Recycleable {
+  value: RecycledInner<&'a Mutex<Vec<T>>, T>
+}
+
+macro_rules! impl_recycled {
+  ($name: ident, $typ: ty, $pool: ty) => {
+  impl <'a, T> AsRef<T> for $typ where T : Recycleable {
+     fn as_ref(&self) -> &T {
+      self.value.as_ref()
+    }
+  }
+
+  impl <'a, T> AsMut<T> for $typ where T : Recycleable {
+     fn as_mut(&mut self) -> &mut T {
+      self.value.as_mut()
+    }
+  }
+
+  impl <'a, T> fmt::Debug for $typ where T : fmt::Debug + Recycleable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      let mut debug = f.debug_struct(stringify!($name));
+      match self.value.value {
+        Some(ref value) => debug.field("present", &true).field("value", value),
+        None => debug.field("present", &false)
+      };
+      debug.finish()
+    }
+  }
+
+  impl <'a, T> fmt::Display for $typ where T : fmt::Display + Recycleable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      self.value.fmt(f)
+    }
+  }
+
+  impl <'a, T> Deref for $typ where T : Recycleable {
+    type Target = T;
+    #[inline] 
+    fn deref(&self) -> &T {
+      self.as_ref()
+    }
+  }
+
+  impl <'a, T> DerefMut for $typ where T : Recycleable {
+    #[inline] 
+    fn deref_mut(&mut self) -> &mut T {
+      self.as_mut()
+    }
+  }
+
+  impl <'a, T> $typ where T: Recycleable {
+    pub fn new(pool: $pool, value: T) -> $typ {
+      $name { value: RecycledInner::new(pool, value) }
+    }
+    
+    #[inline] 
+    pub fn new_from<A>(pool: $pool, value: T, source: A) -> $typ where T : InitializeWith<A> {
+      $name { value: RecycledInner::new_from(pool, value, source) }
+    }
+
+    #[inline] 
+    pub fn detach(self) -> T {
+      self.value.detach()
+    }
+
+    #[inline] 
+    pub fn try_as_ref(&self) -> Result<&T, Error> {
+      self.value.try_as_ref()
+    }
+
+    #[inline] 
+    pub fn try_as_mut(&mut self) -> Result<&mut T, Error> {
+      self.value.try_as_mut()
+    }
+
+    /// Moves the value out of the guard without returning it to the pool.
+    ///
+    /// The guard is inert afterward: dropping it does nothing, and
+    /// dereferencing it panics.
+    #[inline] 
+    pub fn take(&mut self) -> Option<T> {
+      self.value.take()
+    }
+
+    /// Passes the value to `f` by value and stores the value `f` returns
+    /// back in the guard.
+    ///
+    /// If `f` panics the value is lost and the guard is left empty, so
+    /// dropping it returns nothing to the pool.
+    #[inline] 
+    pub fn with_owned<F>(&mut self, f: F) where F: FnOnce(T) -> T {
+      self.value.with_owned(f)
+    }
+
+    #[inline] 
+    pub fn detach_into(self, slot: &mut Option<T>) {
+      self.value.detach_into(slot)
+    }
+
+    /// Promises that the value is already in a reset state, so dropping the
+    /// guard returns it to the pool without calling `Recycleable::reset`.
+    ///
+    /// Nothing checks the promise; whatever the value holds when the guard
+    /// is dropped is handed to the next checkout.
+    #[inline] 
+    pub fn mark_clean(&mut self) {
+      self.value.clean = true;
+    }
+
+    #[inline] 
+    pub fn detach_reset(self) -> T {
+      self.value.detach_reset()
+    }
+  }
+}
+}
+impl_recycled!{ RcRecycled, RcRecycled<T>, Rc<RefCell<CappedCollection<T>>> }
+impl_recycled!{ Recycled, Recycled<'a, T>, &'a RefCell<CappedCollection<T>> }
+impl_recycled!{ WeakRecycled, WeakRecycled<T>, Weak<RefCell<CappedCollection<T>>> }
+impl_recycled!{ SyncRecycled, SyncRecycled<'a, T>, &'a Mutex<Vec<T>> }
+
+impl <'a, T> Recycled<'a, T> where T: Recycleable {
+  #[inline] 
+  pub fn rebind_to(self, pool: &Pool<T>) -> RcRecycled<T> {
+    pool.attach_rc(self.detach())
+  }
+}
+
+macro_rules! impl_recycled_collections {
+  ($string: ty, $vec: ty, $bytes: ty) => {
+  impl <'a> $string {
+    #[inline] 
+    pub fn push_str(&mut self, string: &str) {
+      self.as_mut().push_str(string)
+    }
+
+    #[inline] 
+    pub fn push(&mut self, ch: char) {
+      self.as_mut().push(ch)
+    }
+
+    #[inline] 
+    pub fn capacity(&self) -> usize {
+      self.as_ref().capacity()
+    }
+  }
+
+  impl <'a, T> $vec {
+    #[inline] 
+    pub fn push(&mut self, value: T) {
+      self.as_mut().push(value)
+    }
+
+    #[inline] 
+    pub fn capacity(&self) -> usize {
+      self.as_ref().capacity()
+    }
+  }
+
+  impl <'a> PartialEq<str> for $string {
+    #[inline] 
+    fn eq(&self, other: &str) -> bool {
+      self.as_ref()[..] == *other
+    }
+  }
+
+  impl <'a, 'b> PartialEq<&'b str> for $string {
+    #[inline] 
+    fn eq(&self, other: &&'b str) -> bool {
+      self.as_ref()[..] == **other
+    }
+  }
+
+  impl <'a> PartialEq<$string> for str {
+    #[inline] 
+    fn eq(&self, other: &$string) -> bool {
+      *self == other.as_ref()[..]
+    }
+  }
+
+  impl <'a, 'b> PartialEq<$string> for &'b str {
+    #[inline] 
+    fn eq(&self, other: &$string) -> bool {
+      **self == other.as_ref()[..]
+    }
+  }
+
+  impl <'a> fmt::Write for $string {
+    #[inline] 
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+      self.push_str(string);
+      Ok(())
+    }
+  }
+
+  impl <'a, T> PartialEq<[T]> for $vec where T: PartialEq {
+    #[inline] 
+    fn eq(&self, other: &[T]) -> bool {
+      self.as_ref()[..] == *other
+    }
+  }
+
+  impl <'a, T> PartialEq<$vec> for [T] where T: PartialEq {
+    #[inline] 
+    fn eq(&self, other: &$vec) -> bool {
+      *self == other.as_ref()[..]
+    }
+  }
+
+  impl <'a> $bytes {
+    #[inline] 
+    pub fn reader(&self) -> impl io::Read + '_ {
+      io::Cursor::new(&self.as_ref()[..])
+    }
+
+    #[inline] 
+    pub fn buf_reader(&self) -> impl io::BufRead + '_ {
+      io::Cursor::new(&self.as_ref()[..])
+    }
+
+    #[cfg(feature = "bytes")]
+    #[inline] 
+    pub fn buf(&self) -> impl bytes::Buf + '_ {
+      &self.as_ref()[..]
+    }
+  }
+
+  #[cfg(feature = "bytes")]
+  unsafe impl <'a> bytes::BufMut for $bytes {
+    #[inline] 
+    fn remaining_mut(&self) -> usize {
+      self.as_ref().remaining_mut()
+    }
+
+    #[inline] 
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+      self.as_mut().advance_mut(cnt)
+    }
+
+    #[inline] 
+    fn chunk_mut(&mut self) -> &mut bytes::buf::UninitSlice {
+      self.as_mut().chunk_mut()
+    }
+
+    #[inline] 
+    fn put_slice(&mut self, src: &[u8]) {
+      self.as_mut().put_slice(src)
+    }
+  }
+}
+}
+impl_recycled_collections!{ RcRecycled<String>, RcRecycled<Vec<T>>, RcRecycled<Vec<u8>> }
+impl_recycled_collections!{ Recycled<'a, String>, Recycled<'a, Vec<T>>, Recycled<'a, Vec<u8>> }
+impl_recycled_collections!{ WeakRecycled<String>, WeakRecycled<Vec<T>>, WeakRecycled<Vec<u8>> }
+impl_recycled_collections!{ SyncRecycled<'a, String>, SyncRecycled<'a, Vec<T>>, SyncRecycled<'a, Vec<u8>> }
+
+trait PoolHandle<T> {
+  fn return_value(&self, value: T);
+  #[inline] 
+  fn before_reset(&self, _value: &mut T) {}
+  // Called when a guard takes ownership of a value and when it gives it up,
+  // by being dropped or detached.
+  #[inline] 
+  fn record_checkout(&self) {}
+  #[inline] 
+  fn record_release(&self) {}
+}
+
+type Supplier<T> = Box<dyn Fn() -> T>;
+type Hook<T> = Box<dyn Fn(&mut T)>;
+
+pub struct CappedCollection<T> where T: Recycleable {
+  values: Vec<T>,
+  max: usize,
+  max_element_capacity: Option<usize>,
+  supplier: Option<Supplier<T>>,
+  before_reset: Option<Hook<T>>,
+  after_reset: Option<Hook<T>>,
+  ttl: Option<Duration>,
+  // When a `ttl` is set, the time each idle value was returned, in the same
+  // order as `values`. Empty otherwise.
+  returned_at: Vec<Instant>,
+  #[cfg(feature = "metrics")]
+  name: &'static str
+}
+
+impl <T> CappedCollection<T> where T: Recycleable {
+  #[inline] 
+  fn is_oversized(&self, value: &T) -> bool {
+    match self.max_element_capacity {
+      Some(max) => value.capacity() > max,
+      None => false
+    }
+  }
+
+  #[inline] 
+  fn create(&self) -> T {
+    match self.supplier {
+      Some(ref supplier) => supplier(),
+      None => T::new()
+    }
+  }
+
+  #[inline] 
+  fn before_reset(&self, value: &mut T) {
+    if let Some(ref before_reset) = self.before_reset {
+      before_reset(value);
+    }
+  }
+
+  #[cfg(feature = "metrics")]
+  #[inline] 
+  fn record_checked_out(&self, delta: f64) {
+    pool_metric!(gauge, "lifeguard.pool.checked_out", self.name, increment, delta);
+  }
+
+  #[inline] 
+  fn record_size(&self) {
+    pool_metric!(gauge, "lifeguard.pool.size", self.name, set, self.values.len() as f64);
+  }
+
+  #[inline] 
+  fn push(&mut self, value: T) {
+    if self.ttl.is_some() {
+      self.returned_at.push(Instant::now());
+    }
+    self.values.push(value);
+    self.record_size();
+  }
+
+  #[inline] 
+  fn pop(&mut self) -> Option<T> {
+    self.returned_at.pop();
+    let value = self.values.pop();
+    self.record_size();
+    value
+  }
+
+  #[inline] 
+  fn truncate(&mut self, len: usize) {
+    self.values.truncate(len);
+    self.returned_at.truncate(len);
+    self.record_size();
+  }
+
+  fn extract<F>(&mut self, mut pred: F) -> Vec<T> where F: FnMut(&T) -> bool {
+    if self.ttl.is_none() {
+      let extracted = self.values.extract_if(.., |value| pred(value)).collect();
+      self.record_size();
+      return extracted;
+    }
+    let values = mem::take(&mut self.values);
+    let returned_at = mem::take(&mut self.returned_at);
+    let mut extracted = Vec::new();
+    for (value, returned) in values.into_iter().zip(returned_at) {
+      if pred(&value) {
+        extracted.push(value);
+      } else {
+        self.values.push(value);
+        self.returned_at.push(returned);
+      }
+    }
+    self.record_size();
+    extracted
+  }
+
+  #[inline] 
+  fn insert(&mut self, mut value: T) {
+    if self.is_oversized(&value) {
+      pool_log!(debug, "dropping oversized value returned to Pool<{}>", type_name::<T>());
+    } else if self.values.len() < self.max {
+      if let Some(ref after_reset) = self.after_reset {
+        after_reset(&mut value);
+      }
+      self.push(value);
+      pool_log!(trace, "return to Pool<{}>, size: {}", type_name::<T>(), self.values.len());
+    } else {
+      pool_log!(debug, "Pool<{}> is full at {}, dropping returned value", type_name::<T>(), self.max);
+    }
+  }
+}
+
+#[inline] 
+fn return_to<T>(values: &RefCell<CappedCollection<T>>, value: T) where T: Recycleable {
+  values.borrow_mut().insert(value);
+}
+
+impl <T> PoolHandle<T> for &RefCell<CappedCollection<T>> where T: Recycleable {
+  #[inline] 
+  fn return_value(&self, value: T) {
+    return_to(self, value);
+  }
+
+  #[inline] 
+  fn before_reset(&self, value: &mut T) {
+    self.borrow().before_reset(value);
+  }
+
+  #[cfg(feature = "metrics")]
+  #[inline] 
+  fn record_checkout(&self) {
+    self.borrow().record_checked_out(1.0);
+  }
+
+  #[cfg(feature = "metrics")]
+  #[inline] 
+  fn record_release(&self) {
+    self.borrow().record_checked_out(-1.0);
+  }
+}
+
+impl <T> PoolHandle<T> for Rc<RefCell<CappedCollection<T>>> where T: Recycleable {
+  #[inline] 
+  fn return_value(&self, value: T) {
+    return_to(self, value);
+  }
+
+  #[inline] 
+  fn before_reset(&self, value: &mut T) {
+    self.borrow().before_reset(value);
+  }
+
+  #[cfg(feature = "metrics")]
+  #[inline] 
+  fn record_checkout(&self) {
+    self.borrow().record_checked_out(1.0);
+  }
+
+  #[cfg(feature = "metrics")]
+  #[inline] 
+  fn record_release(&self) {
+    self.borrow().record_checked_out(-1.0);
+  }
+}
+
+impl <T> PoolHandle<T> for Weak<RefCell<CappedCollection<T>>> where T: Recycleable {
+  #[inline] 
+  fn return_value(&self, value: T) {
+    if let Some(values) = self.upgrade() {
+      return_to(&values, value);
+    } else {
+      pool_log!(debug, "Pool<{}> is gone, dropping returned value", type_name::<T>());
+    }
+  }
+
+  #[inline] 
+  fn before_reset(&self, value: &mut T) {
+    if let Some(values) = self.upgrade() {
+      values.borrow().before_reset(value);
+    }
+  }
+
+  #[cfg(feature = "metrics")]
+  #[inline] 
+  fn record_checkout(&self) {
+    if let Some(values) = self.upgrade() {
+      values.borrow().record_checked_out(1.0);
+    }
+  }
+
+  #[cfg(feature = "metrics")]
+  #[inline] 
+  fn record_release(&self) {
+    if let Some(values) = self.upgrade() {
+      values.borrow().record_checked_out(-1.0);
+    }
+  }
+}
+
+#[inline] 
+fn lock<T>(values: &Mutex<Vec<T>>) -> MutexGuard<'_, Vec<T>> {
+  // The lock is never held while user code runs, so a poisoned pool is
+  // still in a consistent state.
+  match values.lock() {
+    Ok(guard) => guard,
+    Err(poisoned) => poisoned.into_inner()
+  }
+}
+
+impl <T> PoolHandle<T> for &Mutex<Vec<T>> where T: Recycleable {
+  #[inline] 
+  fn return_value(&self, value: T) {
+    lock(self).push(value);
+  }
+}
+
+struct RecycledInner<P, T> where P: PoolHandle<T>, T : Recycleable {
+  value: Option<T>,
+  pool: P,
+  clean: bool
+}
+
+impl <P, T> Drop for RecycledInner<P, T> where P: PoolHandle<T>, T : Recycleable {
+  #[inline] 
+  fn drop(&mut self) {
+    if let Some(mut value) = self.value.take() {
+      self.pool.record_release();
+      if !self.clean {
+        self.pool.before_reset(&mut value);
+        value.reset();
+        debug_assert!(value.is_reset(), "Recycleable::reset left the value in a non-reset state.");
+      }
+      self.pool.return_value(value);
+    }
+  }
+}
+
+impl <P, T> AsRef<T> for RecycledInner<P, T> where P: PoolHandle<T>, T : Recycleable {
+   fn as_ref(&self) -> &T {
+    match self.try_as_ref() {
+      Ok(v) => v,
+      Err(e) => panic!("{}", e)
+    }
+  }
+}
+
+impl <P, T> AsMut<T> for RecycledInner<P, T> where P: PoolHandle<T>, T : Recycleable {
+   fn as_mut(&mut self) -> &mut T {
+    match self.try_as_mut() {
+      Ok(v) => v,
+      Err(e) => panic!("{}", e)
+    }
+  }
+}
+
+impl <P, T> fmt::Display for RecycledInner<P, T> where P: PoolHandle<T>, T : fmt::Display + Recycleable {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self.value {
+      Some(ref s) => s.fmt(f),
+      None => write!(f, "Empty Recycled<T>")
+    }
+  }
+}
+
+impl <P, T> Deref for RecycledInner<P, T> where P: PoolHandle<T>, T : Recycleable {
+  type Target = T;
+  #[inline] 
+  fn deref(&self) -> &T {
+    self.as_ref()
+  }
+}
+
+impl <P, T> DerefMut for RecycledInner<P, T> where P: PoolHandle<T>, T : Recycleable {
+  #[inline] 
+  fn deref_mut(&mut self) -> &mut T {
+    self.as_mut()
+  }
+}
+
+impl <P, T> RecycledInner<P, T> where P: PoolHandle<T>, T : Recycleable {
+  #[inline] 
+  fn new(pool: P, value: T) -> RecycledInner<P, T> {
+    pool.record_checkout();
+    RecycledInner {
+      value: Some(value),
+      pool,
+      clean: false
+    }
+  }
+  
+  #[inline] 
+  fn new_from<A>(pool: P, mut value: T, source: A) -> RecycledInner<P, T> where T : InitializeWith<A> {
+    value.initialize_with(source);
+    pool.record_checkout();
+    RecycledInner {
+      value: Some(value),
+      pool,
+      clean: false
+    }
+  }
+
+  #[inline] 
+  fn try_as_ref(&self) -> Result<&T, Error> {
+    self.value.as_ref().ok_or(Error::MissingValue)
+  }
+
+  #[inline] 
+  fn try_as_mut(&mut self) -> Result<&mut T, Error> {
+    self.value.as_mut().ok_or(Error::MissingValue)
+  }
+
+  #[inline] 
+  fn take(&mut self) -> Option<T> {
+    let value = self.value.take();
+    if value.is_some() {
+      self.pool.record_release();
+    }
+    value
+  }
+
+  #[inline] 
+  fn with_owned<F>(&mut self, f: F) where F: FnOnce(T) -> T {
+    let value = match self.value.take() {
+      Some(v) => v,
+      None => panic!("{}", Error::MissingValue)
+    };
+    // If `f` panics the value is lost, so it no longer counts as checked out.
+    let release = ReleaseOnUnwind { pool: &self.pool, value: PhantomData };
+    let value = f(value);
+    mem::forget(release);
+    self.value = Some(value);
+  }
+
+  #[inline] 
+  fn detach_into(mut self, slot: &mut Option<T>) {
+    *slot = self.take();
+  }
+
+  #[inline] 
+  fn detach(mut self) -> T {
+    let value = self.take().unwrap();
+    drop(self);
+    value
+  }
+
+  #[inline] 
+  fn detach_reset(mut self) -> T {
+    let mut value = self.take().unwrap();
+    // Reset the value the same way as when it is returned, so any hook the
+    // pool has, like an element recycler, still sees it.
+    self.pool.before_reset(&mut value);
+    value.reset();
+    drop(self);
+    value
+  }
+}
+
+struct ReleaseOnUnwind<'a, P, T> where P: PoolHandle<T> + 'a {
+  pool: &'a P,
+  value: PhantomData<fn() -> T>
+}
+
+impl <'a, P, T> Drop for ReleaseOnUnwind<'a, P, T> where P: PoolHandle<T> + 'a {
+  #[inline] 
+  fn drop(&mut self) {
+    self.pool.record_release();
+  }
+}
+
+/// Identifies a `Pool`, so values detached from it can be checked when they
+/// are handed back to `Pool::reclaim`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PoolId(usize);
+
+static NEXT_POOL_ID: AtomicUsize = AtomicUsize::new(0);
+
+impl PoolId {
+  fn next() -> PoolId {
+    PoolId(NEXT_POOL_ID.fetch_add(1, Ordering::Relaxed))
+  }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PoolStats {
+  pub hits: u64,
+  pub misses: u64,
+  /// Time spent allocating fresh values. Always 0 unless the `alloc-timing`
+  /// feature is enabled.
+  pub alloc_time_nanos: u64
+}
+
+pub struct PoolBuilder<T> where T : Recycleable {
+  warm: usize,
+  max: usize,
+  max_element_capacity: Option<usize>,
+  supplier: Option<Supplier<T>>,
+  before_reset: Option<Hook<T>>,
+  after_reset: Option<Hook<T>>,
+  ttl: Option<Duration>,
+  #[cfg(feature = "metrics")]
+  name: Option<&'static str>
+}
+
+impl <T> PoolBuilder<T> where T: Recycleable {
+  #[inline] 
+  pub fn warm(mut self, warm: usize) -> PoolBuilder<T> {
+    self.warm = warm;
+    self
+  }
+
+  #[inline] 
+  pub fn max(mut self, max: usize) -> PoolBuilder<T> {
+    self.max = max;
+    self
+  }
+
+  #[inline] 
+  pub fn max_element_capacity(mut self, max_element_capacity: usize) -> PoolBuilder<T> {
+    self.max_element_capacity = Some(max_element_capacity);
+    self
+  }
+
+  #[inline] 
+  pub fn supplier<F>(mut self, supplier: F) -> PoolBuilder<T> where F: Fn() -> T + 'static {
+    self.supplier = Some(Box::new(supplier));
+    self
+  }
+
+  /// Timestamps idle values when they are returned, so that
+  /// `Pool::evict_expired` can drop the ones idle for longer than `ttl`.
+  #[inline] 
+  pub fn ttl(mut self, ttl: Duration) -> PoolBuilder<T> {
+    self.ttl = Some(ttl);
+    self
+  }
+
+  /// Sets the `pool` label attached to the pool's metrics. Pools that are
+  /// not named are labelled with the name of their value type.
+  #[cfg(feature = "metrics")]
+  #[inline] 
+  pub fn name(mut self, name: &'static str) -> PoolBuilder<T> {
+    self.name = Some(name);
+    self
+  }
+
+  fn capacity_floor_with<C, R>(mut self, floor: usize, create: C, reserve: R) -> PoolBuilder<T>
+    where C: Fn(usize) -> T + 'static, R: Fn(&mut T, usize) + 'static {
+    self.supplier = Some(Box::new(move || create(floor)));
+    self.after_reset = Some(Box::new(move |value: &mut T| {
+      if Recycleable::capacity(value) < floor {
+        reserve(value, floor);
+      }
+    }));
+    self
+  }
+
+  pub fn build(self) -> Pool<T> {
+    let mut collection = CappedCollection {
+      values: Vec::new(),
+      max: self.max,
+      max_element_capacity: self.max_element_capacity,
+      supplier: self.supplier,
+      before_reset: self.before_reset,
+      after_reset: self.after_reset,
+      ttl: self.ttl,
+      returned_at: Vec::new(),
+      #[cfg(feature = "metrics")]
+      name: self.name.unwrap_or_else(type_name::<T>)
+    };
+    for _ in 0..cmp::min(self.warm, self.max) {
+      let value = collection.create();
+      collection.push(value);
+    }
+    Pool {
+      values: Rc::new(RefCell::new(collection)),
+      stats: Cell::new(PoolStats::default()),
+      id: PoolId::next()
+    }
+  }
+}
+
+impl <T> PoolBuilder<Vec<T>> {
+  /// Makes every value the pool hands out have a capacity of at least
+  /// `floor`. This replaces any supplier set earlier.
+  #[inline] 
+  pub fn capacity_floor(self, floor: usize) -> PoolBuilder<Vec<T>> {
+    self.capacity_floor_with(floor, |floor| Vec::with_capacity(floor), |value, floor| value.reserve(floor))
+  }
+}
+
+impl <T> PoolBuilder<Vec<T>> {
+  /// Hands each element of a returned vector to `recycler`, instead of
+  /// dropping it when the vector is cleared.
+  ///
+  /// `recycler` can, for example, return the elements to a pool of their
+  /// own. It runs while this pool is borrowed, so it must not use this pool.
+  #[inline] 
+  pub fn element_recycler<F>(mut self, recycler: F) -> PoolBuilder<Vec<T>> where F: Fn(T) + 'static {
+    self.before_reset = Some(Box::new(move |value: &mut Vec<T>| {
+      for element in value.drain(..) {
+        recycler(element);
+      }
+    }));
+    self
+  }
+}
+
+impl PoolBuilder<String> {
+  /// Makes every value the pool hands out have a capacity of at least
+  /// `floor`. This replaces any supplier set earlier.
+  #[inline] 
+  pub fn capacity_floor(self, floor: usize) -> PoolBuilder<String> {
+    self.capacity_floor_with(floor, String::with_capacity, String::reserve)
+  }
+}
+
+/// A pool of reusable values.
+///
+/// A `Pool` cannot be built by a `const fn`: its values live behind an `Rc`
+/// so that `RcRecycled` and `WeakRecycled` guards can share them, and
+/// `Rc::new` allocates. The pool is also neither `Sync` nor `Send`, so it
+/// cannot be stored in a `static` at all. A module-level pool is instead
+/// declared per thread, where `thread_local!` provides the lazy
+/// initialization:
+///
+/// ```
+/// # extern crate lifeguard;
+/// # use lifeguard::Pool;
+/// thread_local! {
+///   static POOL: Pool<String> = Pool::with_size(0);
+/// }
+///
+/// # fn main() {
+/// POOL.with(|pool| {
+///   let string = pool.new_from("cat");
+///   assert_eq!("cat", *string);
+/// });
+/// # }
+/// ```
+pub struct Pool <T> where T : Recycleable {
+  values: Rc<RefCell<CappedCollection<T>>>,
+  stats: Cell<PoolStats>,
+  id: PoolId
+}
+
+impl <T> Pool <T>
+  where T: Recycleable {
+
+  #[inline]
+  pub fn with_size(size: u32) -> Pool <T> {
+    Pool::builder().warm(size as usize).build()
+  }
+
+  #[inline]
+  pub fn builder() -> PoolBuilder<T> {
+    PoolBuilder {
+      warm: 0,
+      max: usize::MAX,
+      max_element_capacity: None,
+      supplier: None,
+      before_reset: None,
+      after_reset: None,
+      ttl: None,
+      #[cfg(feature = "metrics")]
+      name: None
+    }
+  }
+
+  #[inline]
+  pub fn with_size_and_supplier<F>(size: u32, supplier: F) -> Pool <T> where F: Fn() -> T + 'static {
+    Pool::builder().warm(size as usize).supplier(supplier).build()
+  }
+
+  #[inline] 
+  pub fn attach_rc(&self, value: T) -> RcRecycled<T> {
+    let pool_reference = self.values.clone();
+    RcRecycled { value: RecycledInner::new(pool_reference, value) }
+  }
+
+  #[inline] 
+  pub fn new_rc(&self) -> RcRecycled<T> {
+    let t = self.detached();
+    let pool_reference = self.values.clone();
+    RcRecycled { value: RecycledInner::new(pool_reference, t) }
+  }
+ 
+  #[inline(always)] 
+  pub fn new_rc_from<A>(&self, source: A) -> RcRecycled<T> where T: InitializeWith<A> {
+    let t = self.detached();
+    let pool_reference = self.values.clone();
+    RcRecycled { value: RecycledInner::new_from(pool_reference, t, source) }
+  }
+
+  /// Checks out a value that can be shared between several owners.
+  ///
+  /// The value is reset and returned to the pool only when the last `Rc`
+  /// pointing at it is dropped, not when any one owner lets go of it.
+  #[inline] 
+  pub fn new_shared(&self) -> SharedRecycled<T> {
+    Rc::new(self.new_rc())
+  }
+
+  #[inline] 
+  pub fn new_shared_from<A>(&self, source: A) -> SharedRecycled<T> where T: InitializeWith<A> {
+    Rc::new(self.new_rc_from(source))
+  }
+
+  #[inline] 
+  pub fn new_weak(&self) -> WeakRecycled<T> {
+    let t = self.detached();
+    let pool_reference = Rc::downgrade(&self.values);
+    WeakRecycled { value: RecycledInner::new(pool_reference, t) }
+  }
+
+  #[inline] 
+  pub fn attach(&self, value: T) -> Recycled<'_, T> {
+    Recycled { value: RecycledInner::new(&*self.values, value) }
+  }
+
+  #[inline] 
+  #[allow(clippy::new_ret_no_self)]
+  pub fn new(&self) -> Recycled<'_, T> {
+    let t = self.detached();
+    Recycled { value: RecycledInner::new(&*self.values, t) }
+  }
+
+  #[inline(always)] 
+  pub fn new_from<A>(&self, source: A) -> Recycled<'_, T> where T: InitializeWith<A> {
+    let t = self.detached();
+    Recycled { value: RecycledInner::new_from(&*self.values, t, source) }
+  }
+
+  #[inline] 
+  pub fn try_new_from<E, F>(&self, init: F) -> Result<Recycled<'_, T>, E> where F: FnOnce(&mut T) -> Result<(), E> {
+    let mut recycled = self.new();
+    // On failure the guard is dropped here, returning the value to the pool.
+    init(&mut recycled)?;
+    Ok(recycled)
+  }
+
+  #[inline] 
+  fn pop(&self) -> Option<T> {
+    let mut stats = self.stats.get();
+    let mut collection = self.values.borrow_mut();
+    let value = collection.pop();
+    if value.is_some() {
+      stats.hits += 1;
+      pool_log!(trace, "checkout hit from Pool<{}>, size: {}", type_name::<T>(), collection.values.len());
+      pool_metric!(counter, "lifeguard.pool.hits", collection.name, increment, 1);
+    } else {
+      stats.misses += 1;
+      pool_log!(trace, "checkout miss from Pool<{}>, size: {}", type_name::<T>(), collection.values.len());
+      pool_metric!(counter, "lifeguard.pool.misses", collection.name, increment, 1);
+    }
+    self.stats.set(stats);
+    value
+  }
+
+  #[inline] 
+  fn timed<F, R>(&self, create: F) -> R where F: FnOnce() -> R {
+    #[cfg(feature = "alloc-timing")]
+    let start = Instant::now();
+    let value = create();
+    #[cfg(feature = "alloc-timing")]
+    {
+      let mut stats = self.stats.get();
+      stats.alloc_time_nanos += start.elapsed().as_nanos() as u64;
+      self.stats.set(stats);
+    }
+    value
+  }
+
+  #[inline] 
+  pub fn detached(&self) -> T {
+    match self.pop() {
+      Some(v) => v,
+      None => self.timed(|| self.values.borrow().create())
+    }
+  }
+
+  #[inline] 
+  pub fn try_detached(&self) -> Result<T, T::Error> where T: TryRecycleable {
+    match self.pop() {
+      Some(v) => Ok(v),
+      None => self.timed(T::try_new)
+    }
+  }
+
+  /// Checks out `n` owned values, allocating any the pool can't supply.
+  ///
+  /// The values are not tracked by a guard; hand them back with `return_all`
+  /// once they are no longer needed.
+  pub fn take_n(&self, n: usize) -> Vec<T> {
+    (0..n).map(|_| self.detached()).collect()
+  }
+
+  /// Resets each value and returns it to the pool, as if a guard holding it
+  /// had been dropped.
+  pub fn return_all<I>(&self, values: I) where I: IntoIterator<Item = T> {
+    let mut collection = self.values.borrow_mut();
+    for mut value in values {
+      collection.before_reset(&mut value);
+      value.reset();
+      debug_assert!(value.is_reset(), "Recycleable::reset left the value in a non-reset state.");
+      collection.insert(value);
+    }
+  }
+
+  /// Returns a value detached from the pool identified by `id`, resetting
+  /// it like `return_all` does.
+  ///
+  /// In debug builds this panics if `id` belongs to a different pool.
+  #[inline] 
+  pub fn reclaim(&self, value: T, id: PoolId) {
+    debug_assert_eq!(self.id, id, "value reclaimed by a pool it was not detached from.");
+    self.return_all(Some(value));
+  }
+
+  #[inline] 
+  pub fn try_new(&self) -> Result<Recycled<'_, T>, T::Error> where T: TryRecycleable {
+    let t = self.try_detached()?;
+    Ok(Recycled { value: RecycledInner::new(&*self.values, t) })
+  }
+
+  #[inline] 
+  pub fn id(&self) -> PoolId {
+    self.id
+  }
+
+  #[inline] 
+  pub fn stats(&self) -> PoolStats {
+    self.stats.get()
+  }
+
+  #[inline] 
+  pub fn size(&self) -> usize {
+    (*self.values).borrow().values.len()
+  }
+
+  /// Like `size`, but returns `None` instead of panicking while the pool is
+  /// mutably borrowed. That is the case in any user code the pool runs while
+  /// it mutates its storage. Examples are `retain` and `drain_filter`
+  /// predicates, suppliers called by `resize`, and `Drop` impls of values
+  /// the pool discards.
+  #[inline] 
+  pub fn checked_size(&self) -> Option<usize> {
+    self.values.try_borrow().ok().map(|collection| collection.values.len())
+  }
+
+  #[inline] 
+  pub fn max_size(&self) -> usize {
+    (*self.values).borrow().max
+  }
+
+  #[inline] 
+  pub fn capacity(&self) -> usize {
+    (*self.values).borrow().values.capacity()
+  }
+
+  #[inline] 
+  pub fn reserve(&self, additional: usize) {
+    self.values.borrow_mut().values.reserve(additional);
+  }
+
+  pub fn resize(&self, target: usize) {
+    let mut collection = self.values.borrow_mut();
+    let target = cmp::min(target, collection.max);
+    pool_log!(debug, "resize Pool<{}> from {} to {}", type_name::<T>(), collection.values.len(), target);
+    if target < collection.values.len() {
+      collection.truncate(target);
+    } else {
+      for _ in collection.values.len()..target {
+        let value = collection.create();
+        collection.push(value);
+      }
+    }
+  }
+
+  #[inline] 
+  pub fn into_scoped(self) -> ScopedPool<T> {
+    ScopedPool::from_pool(self)
+  }
+
+  pub fn into_vec(self) -> Vec<T> {
+    match Rc::try_unwrap(self.values) {
+      Ok(collection) => {
+        let mut collection = collection.into_inner();
+        let values = mem::take(&mut collection.values);
+        collection.truncate(0);
+        values
+      }
+      // Outstanding `RcRecycled` guards still share the collection.
+      Err(shared) => {
+        let mut collection = shared.borrow_mut();
+        let values = mem::take(&mut collection.values);
+        collection.truncate(0);
+        values
+      }
+    }
+  }
+
+  #[inline] 
+  pub fn retain<F>(&self, mut pred: F) where F: FnMut(&T) -> bool {
+    self.values.borrow_mut().extract(|value| !pred(value));
+  }
+
+  #[inline] 
+  pub fn drain_filter<F>(&self, mut pred: F) -> Vec<T> where F: FnMut(&T) -> bool {
+    self.values.borrow_mut().extract(|value| pred(value))
+  }
+
+  #[inline] 
+  pub fn set_max_element_capacity(&self, max_element_capacity: Option<usize>) {
+    self.values.borrow_mut().max_element_capacity = max_element_capacity;
+  }
+
+  pub fn sweep(&self) {
+    {
+      let collection = &mut *self.values.borrow_mut();
+      if let Some(max) = collection.max_element_capacity {
+        collection.extract(|value| value.capacity() > max);
+        pool_log!(debug, "swept Pool<{}>, size: {}", type_name::<T>(), collection.values.len());
+      }
+    }
+    self.evict_expired();
+  }
+
+  /// Drops the idle values that have been in the pool for longer than the
+  /// ttl set with `PoolBuilder::ttl`. Does nothing if no ttl was set.
+  pub fn evict_expired(&self) {
+    let collection = &mut *self.values.borrow_mut();
+    if let Some(ttl) = collection.ttl {
+      let now = Instant::now();
+      // Values are pushed and popped at the back, so the oldest come first.
+      let expired = collection.returned_at.partition_point(|&returned| now.duration_since(returned) > ttl);
+      collection.values.drain(..expired);
+      collection.returned_at.drain(..expired);
+      collection.record_size();
+      pool_log!(debug, "evicted {} expired values from Pool<{}>, size: {}", expired, type_name::<T>(), collection.values.len());
+    }
+  }
+}
+  
+
+impl <T> Pool <Vec<T>> {
+
+  #[inline] 
+  pub fn vec(&self) -> Recycled<'_, Vec<T>> {
+    self.new()
+  }
+
+  #[inline] 
+  pub fn vec_from<A>(&self, source: A) -> Recycled<'_, Vec<T>> where Vec<T>: InitializeWith<A> {
+    self.new_from(source)
+  }
+
+  #[inline] 
+  pub fn with_capacity_floor(size: u32, floor: usize) -> Pool <Vec<T>> {
+    Pool::<Vec<T>>::builder().warm(size as usize).capacity_floor(floor).build()
+  }
+
+  /// Creates a pool whose vectors hand each of their elements to `recycler`
+  /// when they are returned. See `PoolBuilder::element_recycler`.
+  #[inline] 
+  pub fn with_element_recycler<F>(size: u32, recycler: F) -> Pool <Vec<T>> where F: Fn(T) + 'static {
+    Pool::<Vec<T>>::builder().warm(size as usize).element_recycler(recycler).build()
+  }
+}
+
+impl <T> Pool <Vec<T>>
+  where T: Clone {
+
+  #[inline] 
+  pub fn new_from_slice(&self, src: &[T]) -> Recycled<'_, Vec<T>> {
+    let mut recycled = self.new();
+    recycled.clear();
+    recycled.extend_from_slice(src);
+    recycled
+  }
+}
+
+impl Pool <String> {
+
+  #[inline] 
+  pub fn string(&self) -> Recycled<'_, String> {
+    self.new()
+  }
+
+  #[inline] 
+  pub fn string_from<A>(&self, source: A) -> Recycled<'_, String> where String: InitializeWith<A> {
+    self.new_from(source)
+  }
+
+  #[inline] 
+  pub fn with_capacity_floor(size: u32, floor: usize) -> Pool <String> {
+    Pool::<String>::builder().warm(size as usize).capacity_floor(floor).build()
+  }
+
+  #[inline] 
+  pub fn new_from_utf8(&self, bytes: &[u8]) -> Result<Recycled<'_, String>, str::Utf8Error> {
+    let s = str::from_utf8(bytes)?;
+    Ok(self.new_from(s))
+  }
+
+  #[inline] 
+  pub fn new_from_char(&self, ch: char) -> Recycled<'_, String> {
+    let mut recycled = self.new();
+    recycled.push(ch);
+    recycled
+  }
+
+  #[inline] 
+  pub fn new_from_chars<I>(&self, chars: I) -> Recycled<'_, String> where I: IntoIterator<Item = char> {
+    let mut recycled = self.new();
+    recycled.extend(chars);
+    recycled
+  }
+
+  /// Checks out a string and formats `args` straight into it, like a
+  /// `format!` that reuses pooled storage.
+  #[inline] 
+  pub fn format(&self, args: fmt::Arguments) -> Recycled<'_, String> {
+    let mut recycled = self.new();
+    // Writing to a `String` only fails if a `Display` impl itself fails.
+    fmt::Write::write_fmt(&mut recycled, args).expect("a formatting trait implementation returned an error");
+    recycled
+  }
+}
+
+impl Pool <Vec<u8>> {
+
+  #[inline] 
+  pub fn new_zeroed(&self, len: usize) -> Recycled<'_, Vec<u8>> {
+    let mut recycled = self.new();
+    recycled.clear();
+    recycled.resize(len, 0);
+    recycled
+  }
+}
+
+impl <T> AnyPool for Pool<T> where T: Recycleable + 'static {
+  #[inline] 
+  fn checkout_any(&self) -> Box<dyn Any> {
+    Box::new(self.new_rc())
+  }
+}
+
+/// A pinned value checked out with `Pool::new_pinned`.
+///
+/// Unlike the other guards this one never hands out a plain `&mut T`, only
+/// `Pin<&mut T>`. The value lives in its own heap allocation for as long as
+/// it is pooled, so returning it to the pool never moves it; it is reset in
+/// place through `PinnedRecycleable::reset`.
+pub struct PinnedRecycled <'a, T> where T : PinnedRecycleable {
+  value: Recycled<'a, Pin<Box<T>>>
+}
+
+impl <'a, T> PinnedRecycled <'a, T>
+  where T: PinnedRecycleable {
+
+  #[inline] 
+  pub fn as_mut(&mut self) -> Pin<&mut T> {
+    self.value.as_mut().as_mut()
+  }
+}
+
+impl <'a, T> Deref for PinnedRecycled <'a, T>
+  where T: PinnedRecycleable {
+  type Target = T;
+  #[inline] 
+  fn deref(&self) -> &T {
+    &self.value
+  }
+}
+
+impl <T> Pool <Pin<Box<T>>>
+  where T: PinnedRecycleable {
+
+  #[inline] 
+  pub fn new_pinned(&self) -> PinnedRecycled<'_, T> {
+    PinnedRecycled { value: self.new() }
+  }
+}
+
+/// A front/back pair of values checked out from a `Pool`.
+///
+/// The back value is written while the front value is read, and `swap`
+/// exchanges them. Neither value is reset by a swap, so the new back value
+/// still holds the previous front contents. Both values are returned to the
+/// pool when the `DoubleBuffer` is dropped.
+pub struct DoubleBuffer <'a, T> where T : Recycleable {
+  front: Recycled<'a, T>,
+  back: Recycled<'a, T>
+}
+
+impl <'a, T> DoubleBuffer <'a, T>
+  where T: Recycleable {
+
+  #[inline] 
+  pub fn new(pool: &'a Pool<T>) -> DoubleBuffer<'a, T> {
+    DoubleBuffer {
+      front: pool.new(),
+      back: pool.new()
+    }
+  }
+
+  #[inline] 
+  pub fn front(&self) -> &T {
+    &self.front
+  }
+
+  #[inline] 
+  pub fn back(&mut self) -> &mut T {
+    &mut self.back
+  }
+
+  #[inline] 
+  pub fn swap(&mut self) {
+    mem::swap(&mut self.front, &mut self.back);
+  }
+}
+
+type Slot<T> = RefCell<Option<RcRecycled<T>>>;
+
+/// A pool that can reclaim every value it has handed out at once, giving
+/// arena-like semantics for request-scoped work.
+///
+/// Each `ScopedRecycled` guard keeps its value in a slot that the pool
+/// tracks through a `Weak` reference. `reset_all` empties every slot that is
+/// still alive, including those of guards that were leaked with
+/// `mem::forget`, and returns the values to the pool. A guard whose value
+/// was reclaimed is not left dangling: it is simply empty, so `try_borrow`
+/// returns `Error::MissingValue`, `borrow` panics and dropping it does
+/// nothing. Dropping the `ScopedPool` reclaims everything the same way.
+///
+/// Values are borrowed through `RefCell`s, so reclaiming never invalidates
+/// a live reference: a value that is borrowed while `reset_all` runs stays
+/// with its guard and is picked up by a later `reset_all`, or returned when
+/// its guard is dropped.
+pub struct ScopedPool <T> where T : Recycleable {
+  pool: Pool<T>,
+  outstanding: RefCell<Vec<Weak<Slot<T>>>>
+}
+
+pub struct ScopedRecycled <T> where T : Recycleable {
+  slot: Rc<Slot<T>>
+}
+
+impl <T> ScopedRecycled <T>
+  where T: Recycleable {
+
+  #[inline] 
+  pub fn is_reclaimed(&self) -> bool {
+    self.slot.borrow().is_none()
+  }
+
+  #[inline] 
+  pub fn try_borrow(&self) -> Result<Ref<'_, T>, Error> {
+    Ref::filter_map(self.slot.borrow(), |slot| slot.as_ref().map(|value| &**value))
+      .map_err(|_| Error::MissingValue)
+  }
+
+  #[inline] 
+  pub fn try_borrow_mut(&mut self) -> Result<RefMut<'_, T>, Error> {
+    RefMut::filter_map(self.slot.borrow_mut(), |slot| slot.as_mut().map(|value| &mut **value))
+      .map_err(|_| Error::MissingValue)
+  }
+
+  #[inline] 
+  pub fn borrow(&self) -> Ref<'_, T> {
+    match self.try_borrow() {
+      Ok(v) => v,
+      Err(e) => panic!("{}", e)
+    }
+  }
+
+  #[inline] 
+  pub fn borrow_mut(&mut self) -> RefMut<'_, T> {
+    match self.try_borrow_mut() {
+      Ok(v) => v,
+      Err(e) => panic!("{}", e)
+    }
+  }
+}
+
+impl <T> ScopedPool <T>
+  where T: Recycleable {
+
+  #[inline] 
+  pub fn with_size(size: u32) -> ScopedPool <T> {
+    ScopedPool::from_pool(Pool::with_size(size))
+  }
+
+  #[inline] 
+  pub fn from_pool(pool: Pool<T>) -> ScopedPool <T> {
+    ScopedPool {
+      pool,
+      outstanding: RefCell::new(Vec::new())
+    }
+  }
+
+  fn track(&self, value: RcRecycled<T>) -> ScopedRecycled<T> {
+    let slot = Rc::new(RefCell::new(Some(value)));
+    let mut outstanding = self.outstanding.borrow_mut();
+    // Forget the slots of guards that were dropped whenever the list would
+    // otherwise have to grow.
+    if outstanding.len() == outstanding.capacity() {
+      outstanding.retain(|slot| slot.strong_count() > 0);
+    }
+    outstanding.push(Rc::downgrade(&slot));
+    ScopedRecycled { slot }
+  }
+
+  #[inline] 
+  #[allow(clippy::new_ret_no_self)]
+  pub fn new(&self) -> ScopedRecycled<T> {
+    self.track(self.pool.new_rc())
+  }
+
+  #[inline] 
+  pub fn new_from<A>(&self, source: A) -> ScopedRecycled<T> where T: InitializeWith<A> {
+    self.track(self.pool.new_rc_from(source))
+  }
+
+  /// Reclaims the values of all outstanding guards, see the type-level
+  /// documentation.
+  pub fn reset_all(&self) {
+    let slots = mem::take(&mut *self.outstanding.borrow_mut());
+    let mut borrowed = Vec::new();
+    for slot in slots.into_iter().filter_map(|slot| slot.upgrade()) {
+      let reclaimed = match slot.try_borrow_mut() {
+        Ok(mut value) => value.take(),
+        Err(_) => {
+          borrowed.push(Rc::downgrade(&slot));
+          None
+        }
+      };
+      // Dropping the inner guard returns the value to the pool.
+      drop(reclaimed);
+    }
+    self.outstanding.borrow_mut().extend(borrowed);
+  }
+
+  /// The number of checked out values that have not been returned yet.
+  pub fn outstanding(&self) -> usize {
+    self.outstanding.borrow().iter()
+      .filter_map(|slot| slot.upgrade())
+      .filter(|slot| slot.try_borrow().map(|value| value.is_some()).unwrap_or(true))
+      .count()
+  }
+
+  #[inline] 
+  pub fn size(&self) -> usize {
+    self.pool.size()
+  }
+}
+
+impl <T> Drop for ScopedPool <T>
+  where T: Recycleable {
+  fn drop(&mut self) {
+    self.reset_all();
+  }
+}
+
+pub struct SyncPool <T> where T : Recycleable {
+  values: Mutex<Vec<T>>
+}
+
+impl <T> SyncPool <T>
+  where T: Recycleable {
+
+  #[inline]
+  pub fn with_size(size: u32) -> SyncPool <T> {
+    let values: Vec<T> = 
+      (0..size)
+      .map(|_| T::new() )
+      .collect();
+    SyncPool {
+      values: Mutex::new(values)
+    }
+  }
+
+  pub fn with_size_parallel(size: u32, threads: usize) -> SyncPool <T> where T: Send {
+    // More threads than values would only start threads with nothing to do.
+    let threads = cmp::max(cmp::min(threads, size as usize), 1);
+    if threads == 1 {
+      return SyncPool::with_size(size);
+    }
+    let size = size as usize;
+    let chunk = size / threads;
+    let remainder = size % threads;
+    let mut values: Vec<T> = Vec::with_capacity(size);
+    thread::scope(|scope| {
+      let workers: Vec<_> = (0..threads)
+        .map(|i| {
+          let count = chunk + if i < remainder { 1 } else { 0 };
+          scope.spawn(move || (0..count).map(|_| T::new()).collect::<Vec<T>>())
+        })
+        .collect();
+      for worker in workers {
+        match worker.join() {
+          Ok(chunk) => values.extend(chunk),
+          Err(panic) => ::std::panic::resume_unwind(panic)
+        }
+      }
+    });
+    SyncPool {
+      values: Mutex::new(values)
+    }
+  }
+
+  #[inline] 
+  pub fn attach(&self, value: T) -> SyncRecycled<'_, T> {
+    SyncRecycled { value: RecycledInner::new(&self.values, value) }
+  }
+
+  #[inline] 
+  #[allow(clippy::new_ret_no_self)]
+  pub fn new(&self) -> SyncRecycled<'_, T> {
+    let t = self.detached();
+    SyncRecycled { value: RecycledInner::new(&self.values, t) }
+  }
+
+  /// Checks out a value and initializes it from `source`.
+  ///
+  /// The pool's lock is only held while the value is popped; it is released
+  /// before `initialize_with` runs, so slow initializers don't block other
+  /// threads using the pool.
+  #[inline(always)] 
+  pub fn new_from<A>(&self, source: A) -> SyncRecycled<'_, T> where T: InitializeWith<A> {
+    let t = self.detached();
+    SyncRecycled { value: RecycledInner::new_from(&self.values, t, source) }
+  }
+
+  /// Checks out a value and passes it to `init` before wrapping it in a
+  /// guard.
+  ///
+  /// As with `new_from`, the pool's lock is released before `init` runs.
+  #[inline] 
+  pub fn new_with<F>(&self, init: F) -> SyncRecycled<'_, T> where F: FnOnce(&mut T) {
+    let mut t = self.detached();
+    init(&mut t);
+    SyncRecycled { value: RecycledInner::new(&self.values, t) }
+  }
+
+  #[inline] 
+  pub fn detached(&self) -> T {
+    let value = lock(&self.values).pop();
+    match value {
+      Some(v) => v,
+      None => T::new()
+    }
+  }
+
+  #[inline] 
+  pub fn size(&self) -> usize {
+    lock(&self.values).len()
   }
 }
-  
@@ -1,8 +1,17 @@
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 extern crate lifeguard;
 
 #[cfg(test)]
 mod tests {
-  use lifeguard::{Pool, RcRecycled, Recycled};
+  use std::cell::Cell;
+  use std::collections::HashMap;
+  use std::io::{BufRead, Read, Write};
+  use std::marker::PhantomPinned;
+  use std::pin::Pin;
+  use std::rc::Rc;
+  use std::thread;
+  use std::time::Duration;
+  use lifeguard::{AlignedBuffer, AnyPool, Buffer, DoubleBuffer, Error, InitializeWith, PinnedRecycleable, Pool, PoolId, PoolStats, RcRecycled, Recycleable, Recycled, ScopedPool, ScopedRecycled, SharedRecycled, SyncPool, SyncRecycled, TryRecycleable, WeakRecycled};
 
   #[test]
   fn test_deref() {
@@ -35,6 +44,235 @@ mod tests {
       assert_eq!("cat", rstring.as_ref());
   }
 
+  #[test]
+  fn test_push_str() {
+      let str_pool : Pool<String> = Pool::with_size(2);
+      let mut rstring = str_pool.new_from("cat");
+      rstring.push_str("s love eating mice");
+      rstring.push('!');
+      assert_eq!("cats love eating mice!", *rstring);
+      let mut rcstring = str_pool.new_rc_from("dog");
+      rcstring.push_str("s chase cats");
+      rcstring.push('!');
+      assert_eq!("dogs chase cats!", *rcstring);
+  }
+
+  #[test]
+  fn test_vec_push() {
+      let vec_pool : Pool<Vec<u32>> = Pool::with_size(2);
+      let mut rvec = vec_pool.new();
+      rvec.push(1);
+      rvec.push(2);
+      assert_eq!(vec![1, 2], *rvec);
+      let mut rcvec = vec_pool.new_rc();
+      rcvec.push(3);
+      assert_eq!(vec![3], *rcvec);
+  }
+
+  #[test]
+  fn test_try_new_from() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      let rstring = str_pool.try_new_from(|s| -> Result<(), ()> {
+        s.push_str("cat");
+        Ok(())
+      }).unwrap();
+      assert_eq!("cat", *rstring);
+      assert_eq!(0, str_pool.size());
+  }
+
+  #[test]
+  fn test_try_new_from_error() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      let result = str_pool.try_new_from(|s| {
+        s.push_str("partial");
+        "12x".parse::<u32>().map(|_| ())
+      });
+      assert!(result.is_err());
+      assert_eq!(1, str_pool.size());
+      assert_eq!("", str_pool.detached());
+  }
+
+  #[test]
+  fn test_reader() {
+      let vec_pool : Pool<Vec<u8>> = Pool::with_size(1);
+      let mut rvec = vec_pool.new();
+      rvec.extend_from_slice(b"cats love mice");
+      let mut reader = rvec.reader();
+      let mut head = [0u8; 4];
+      reader.read_exact(&mut head).unwrap();
+      assert_eq!(b"cats", &head);
+      let mut tail = Vec::new();
+      reader.read_to_end(&mut tail).unwrap();
+      assert_eq!(b" love mice", &tail[..]);
+      assert_eq!(0, reader.read(&mut head).unwrap());
+      assert_eq!(14, rvec.len());
+  }
+
+  #[test]
+  fn test_format() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      {
+        let (a, b) = (7, "cats");
+        let string = str_pool.format(format_args!("{}-{}", a, b));
+        assert_eq!(format!("{}-{}", a, b), *string);
+      }
+      let mut string = str_pool.new_rc();
+      ::std::fmt::Write::write_fmt(&mut string, format_args!("{:>4}", 1)).unwrap();
+      assert_eq!("   1", string);
+      assert_eq!(2, str_pool.stats().hits);
+  }
+
+  #[test]
+  fn test_buf_reader() {
+      let vec_pool : Pool<Vec<u8>> = Pool::with_size(1);
+      let mut rvec = vec_pool.new_rc();
+      rvec.extend_from_slice(b"cats\nlove\nmice");
+      let mut reader = rvec.buf_reader();
+      let mut line = String::new();
+      reader.read_line(&mut line).unwrap();
+      assert_eq!("cats\n", line);
+      assert_eq!(b"love\nmice", reader.fill_buf().unwrap());
+      reader.consume(5);
+      let rest: Vec<String> = reader.lines().map(|l| l.unwrap()).collect();
+      assert_eq!(vec!["mice".to_owned()], rest);
+      assert_eq!(14, rvec.len());
+  }
+
+  #[test]
+  fn test_new_from_slice() {
+      let vec_pool : Pool<Vec<u32>> = Pool::with_size(1);
+      {
+        let rvec = vec_pool.new_from_slice(&[1, 2, 3]);
+        assert_eq!(vec![1, 2, 3], *rvec);
+        assert_eq!(0, vec_pool.size());
+      }
+      assert_eq!(1, vec_pool.size());
+      let vec = vec_pool.detached();
+      assert!(vec.is_empty());
+      assert!(vec.capacity() >= 3);
+  }
+
+  #[test]
+  fn test_fluent_string() {
+      let str_pool : Pool<String> = Pool::with_size(2);
+      let mut rstring = str_pool.string();
+      rstring.push_str("cat");
+      assert_eq!("cat", *rstring);
+      assert_eq!("dog", *str_pool.string_from("dog"));
+  }
+
+  struct Countdown(u32);
+
+  impl InitializeWith<Countdown> for Vec<u32> {
+    fn initialize_with(&mut self, source: Countdown) {
+      self.extend((1..source.0 + 1).rev());
+    }
+  }
+
+  #[test]
+  fn test_fluent_vec() {
+      let vec_pool : Pool<Vec<u32>> = Pool::with_size(2);
+      let mut rvec = vec_pool.vec();
+      rvec.push(1);
+      assert_eq!(vec![1], *rvec);
+      assert_eq!(vec![3, 2, 1], *vec_pool.vec_from(Countdown(3)));
+  }
+
+  #[test]
+  fn test_new_from_utf8() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      let rstring = str_pool.new_from_utf8(b"cat").unwrap();
+      assert_eq!("cat", *rstring);
+      assert!(str_pool.new_from_utf8(&[b'c', 0xff, b't']).is_err());
+      assert_eq!(0, str_pool.size());
+  }
+
+  #[test]
+  fn test_new_from_char() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      let rstring = str_pool.new_from_char('x');
+      assert_eq!("x", *rstring);
+  }
+
+  #[test]
+  fn test_new_from_chars() {
+      let str_pool : Pool<String> = Pool::with_size(2);
+      let rstring = str_pool.new_from_chars(vec!['a', 'b', 'c']);
+      assert_eq!("abc", *rstring);
+      let rstring = str_pool.new_from_chars("mice".chars().rev());
+      assert_eq!("ecim", *rstring);
+  }
+
+  #[test]
+  fn test_new_zeroed() {
+      let vec_pool : Pool<Vec<u8>> = Pool::with_size(1);
+      {
+        let mut rvec = vec_pool.new_zeroed(16);
+        assert_eq!(vec![0u8; 16], *rvec);
+        rvec[0] = 0xff;
+      }
+      let capacity = {
+        let rvec = vec_pool.new_zeroed(8);
+        assert_eq!(vec![0u8; 8], *rvec);
+        rvec.capacity()
+      };
+      assert!(capacity >= 16);
+      assert!(vec_pool.detached().is_empty());
+  }
+
+  #[test]
+  fn test_debug() {
+      let str_pool : Pool<String> = Pool::with_size(2);
+      let mut rstring = str_pool.new_from("cat");
+      assert_eq!("Recycled { present: true, value: \"cat\" }", format!("{:?}", rstring));
+      rstring.take();
+      assert_eq!("Recycled { present: false }", format!("{:?}", rstring));
+      let rcstring = str_pool.new_rc_from("dog");
+      assert_eq!("RcRecycled { present: true, value: \"dog\" }", format!("{:?}", rcstring));
+  }
+
+  #[test]
+  fn test_display() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      let rstring = str_pool.new_from("cat");
+      assert_eq!("cat", format!("{}", rstring));
+  }
+
+  #[test]
+  fn test_eq_str() {
+      let str_pool : Pool<String> = Pool::with_size(2);
+      let rstring = str_pool.new_from("cat");
+      assert!(rstring == "cat");
+      assert!(rstring != "dog");
+      assert!("cat" == rstring);
+      assert!(*"cat" == rstring);
+      assert!(rstring == *"cat");
+      let rcstring = str_pool.new_rc_from("dog");
+      assert!(rcstring == "dog");
+      assert!("dog" == rcstring);
+  }
+
+  #[test]
+  fn test_eq_slice() {
+      let vec_pool : Pool<Vec<u32>> = Pool::with_size(1);
+      let rvec = vec_pool.new_from_slice(&[1, 2, 3]);
+      assert!(rvec == [1, 2, 3][..]);
+      assert!(rvec != [1, 2][..]);
+      assert!([1, 2, 3][..] == rvec);
+  }
+
+  #[test]
+  fn test_guard_capacity() {
+      let str_pool = Pool::with_size_and_supplier(1, || String::with_capacity(32));
+      let rstring = str_pool.new();
+      assert_eq!(rstring.as_ref().capacity(), rstring.capacity());
+      assert!(rstring.capacity() >= 32);
+      let vec_pool = Pool::with_size_and_supplier(1, || Vec::<u8>::with_capacity(16));
+      let rcvec = vec_pool.new_rc();
+      assert_eq!(rcvec.as_ref().capacity(), rcvec.capacity());
+      assert!(rcvec.capacity() >= 16);
+  }
+
   #[test]
   fn test_recycle() {
       let str_pool : Pool<String> = Pool::with_size(1);
@@ -57,6 +295,187 @@ mod tests {
       assert_eq!(0, str_pool.size());
   }
 
+  #[test]
+  fn test_mark_clean() {
+      let str_pool : Pool<String> = Pool::with_size(0);
+      {
+        let mut dirty = str_pool.new_from("cat");
+        let mut clean = str_pool.new_rc_from("dog");
+        dirty.push('s');
+        clean.mark_clean();
+      }
+      let mut strings = str_pool.into_vec();
+      strings.sort();
+      assert_eq!(vec!["".to_owned(), "dog".to_owned()], strings);
+  }
+
+  #[test]
+  fn test_any_pool() {
+      let mut pools : HashMap<&str, Box<dyn AnyPool>> = HashMap::new();
+      pools.insert("string", Box::new(Pool::<String>::with_size(1)));
+      pools.insert("bytes", Box::new(Pool::<Vec<u8>>::with_size(1)));
+      let checkout = pools["string"].checkout_any();
+      assert!(checkout.is::<RcRecycled<String>>());
+      assert!(!checkout.is::<RcRecycled<Vec<u8>>>());
+      let mut string = checkout.downcast::<RcRecycled<String>>().unwrap();
+      string.push_str("cat");
+      assert_eq!("cat", &**string);
+      let bytes = pools["bytes"].checkout_any();
+      assert!(bytes.downcast_ref::<RcRecycled<Vec<u8>>>().unwrap().is_empty());
+  }
+
+  struct Node {
+    value: u32,
+    _pinned: PhantomPinned
+  }
+
+  impl Node {
+    fn set(self: Pin<&mut Self>, value: u32) {
+      unsafe { self.get_unchecked_mut().value = value; }
+    }
+  }
+
+  impl PinnedRecycleable for Node {
+    fn new() -> Node {
+      Node { value: 0, _pinned: PhantomPinned }
+    }
+    fn reset(self: Pin<&mut Self>) {
+      self.set(0);
+    }
+  }
+
+  #[test]
+  fn test_new_pinned() {
+      let node_pool : Pool<Pin<Box<Node>>> = Pool::with_size(1);
+      let address = {
+        let mut node = node_pool.new_pinned();
+        node.as_mut().set(7);
+        assert_eq!(7, node.value);
+        &*node as *const Node
+      };
+      assert_eq!(1, node_pool.size());
+      let node = node_pool.new_pinned();
+      assert_eq!(0, node.value);
+      assert_eq!(address, &*node as *const Node);
+  }
+
+  #[test]
+  fn test_double_buffer() {
+      let vec_pool : Pool<Vec<u8>> = Pool::with_size(2);
+      {
+        let mut frames = DoubleBuffer::new(&vec_pool);
+        assert_eq!(0, vec_pool.size());
+        for frame in 1..4u8 {
+          frames.back().clear();
+          frames.back().push(frame);
+          frames.swap();
+          assert_eq!(&[frame], &frames.front()[..]);
+          assert_eq!(frame - 1, frames.back().first().cloned().unwrap_or(0));
+        }
+      }
+      assert_eq!(2, vec_pool.size());
+  }
+
+  #[test]
+  fn test_stats_literal() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      let _string = str_pool.new();
+      let expected = PoolStats { hits: 1, misses: 0, alloc_time_nanos: 0 };
+      assert_eq!(expected, str_pool.stats());
+  }
+
+  #[test]
+  fn test_take_n() {
+      let str_pool : Pool<String> = Pool::with_size(3);
+      let mut strings = str_pool.take_n(5);
+      assert_eq!(5, strings.len());
+      assert_eq!(0, str_pool.size());
+      assert_eq!(3, str_pool.stats().hits);
+      assert_eq!(2, str_pool.stats().misses);
+      for string in strings.iter_mut() {
+        string.push_str("cat");
+      }
+      str_pool.return_all(strings);
+      assert_eq!(5, str_pool.size());
+      assert!(str_pool.into_vec().iter().all(|s| s.is_empty()));
+  }
+
+  #[test]
+  fn test_take() {
+      struct Holder<'a> {
+        string: Recycled<'a, String>
+      }
+      let str_pool : Pool<String> = Pool::with_size(1);
+      {
+        let mut holder = Holder { string: str_pool.new_from("cat") };
+        assert_eq!(Some("cat".to_owned()), holder.string.take());
+        assert_eq!(None, holder.string.take());
+        assert_eq!(0, str_pool.size());
+      }
+      assert_eq!(0, str_pool.size());
+  }
+
+  #[test]
+  fn test_try_as_ref() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      let mut rstring = str_pool.new_from("cat");
+      assert_eq!(Ok(&"cat".to_owned()), rstring.try_as_ref());
+      rstring.try_as_mut().unwrap().push('s');
+      assert_eq!("cats", *rstring);
+      rstring.take();
+      assert_eq!(Err(Error::MissingValue), rstring.try_as_ref());
+      assert_eq!(Err(Error::MissingValue), rstring.try_as_mut());
+      assert_eq!("Recycled<T> smartpointer missing its value.", Error::MissingValue.to_string());
+  }
+
+  #[test]
+  #[should_panic(expected = "missing its value")]
+  fn test_deref_missing_value() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      let mut rstring = str_pool.new_from("cat");
+      rstring.take();
+      rstring.push('s');
+  }
+
+  fn shout(mut s: String) -> String {
+    s.make_ascii_uppercase();
+    s.push('!');
+    s
+  }
+
+  #[test]
+  fn test_with_owned() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      {
+        let mut rstring = str_pool.new_from("cat");
+        rstring.with_owned(shout);
+        assert_eq!("CAT!", *rstring);
+      }
+      assert_eq!(1, str_pool.size());
+  }
+
+  #[test]
+  fn test_with_owned_panic() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      let mut rstring = str_pool.new_from("cat");
+      let result = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+        rstring.with_owned(|_| panic!("builder failed"));
+      }));
+      assert!(result.is_err());
+      assert!(rstring.try_as_ref().is_err());
+      drop(rstring);
+      assert_eq!(0, str_pool.size());
+  }
+
+  #[test]
+  fn test_detach_into() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      let mut slot = Some("mouse".to_owned());
+      str_pool.new_from("cat").detach_into(&mut slot);
+      assert_eq!(Some("cat".to_owned()), slot);
+      assert_eq!(0, str_pool.size());
+  }
+
   #[test]
   fn test_attach_rc() {
       let str_pool : Pool<String> = Pool::with_size(1);
@@ -68,6 +487,74 @@ mod tests {
       }
       assert_eq!(1, str_pool.size());
   }
+  #[test]
+  fn test_weak_recycle() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      {
+        let mut rstring: WeakRecycled<String> = str_pool.new_weak();
+        rstring.push_str("cat");
+        assert_eq!("cat", *rstring);
+        assert_eq!(0, str_pool.size());
+      }
+      assert_eq!(1, str_pool.size());
+  }
+
+  #[test]
+  fn test_weak_outlives_pool() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      let mut rstring = str_pool.new_weak();
+      drop(str_pool);
+      rstring.push_str("cat");
+      assert_eq!("cat", *rstring);
+      drop(rstring);
+  }
+
+  #[test]
+  fn test_rebind_to() {
+      let owned_pool : Pool<String> = Pool::with_size(0);
+      let escaped: RcRecycled<String> = {
+        let scoped_pool : Pool<String> = Pool::with_size(1);
+        let rstring: Recycled<String> = scoped_pool.new_from("cat");
+        let escaped = rstring.rebind_to(&owned_pool);
+        assert_eq!(0, scoped_pool.size());
+        escaped
+      };
+      assert_eq!("cat", *escaped);
+      assert_eq!(0, owned_pool.size());
+      drop(escaped);
+      assert_eq!(1, owned_pool.size());
+      assert_eq!("", *owned_pool.new());
+  }
+
+  #[test]
+  fn test_shared() {
+      let vec_pool : Pool<Vec<u8>> = Pool::with_size(1);
+      {
+        let mut shared: SharedRecycled<Vec<u8>> = vec_pool.new_shared();
+        ::std::rc::Rc::get_mut(&mut shared).unwrap().extend_from_slice(b"edge");
+        let node_a = shared.clone();
+        let node_b = shared.clone();
+        drop(shared);
+        drop(node_a);
+        assert_eq!(0, vec_pool.size());
+        assert_eq!(b"edge", &node_b[..]);
+      }
+      assert_eq!(1, vec_pool.size());
+      assert!(vec_pool.detached().is_empty());
+  }
+
+  #[test]
+  fn test_shared_from() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      let shared = str_pool.new_shared_from("cat");
+      let other = shared.clone();
+      drop(shared);
+      assert_eq!(0, str_pool.size());
+      assert_eq!("cat", **other);
+      drop(other);
+      assert_eq!(1, str_pool.size());
+  }
+
   #[test]
   fn test_attach() {
       let str_pool : Pool<String> = Pool::with_size(1);
@@ -90,4 +577,854 @@ mod tests {
       }
       assert_eq!(1, str_pool.size());
   }
+
+  #[test]
+  fn test_capacity() {
+      let str_pool : Pool<String> = Pool::with_size(2);
+      assert!(str_pool.capacity() >= 2);
+      str_pool.reserve(16);
+      let capacity = str_pool.capacity();
+      assert!(capacity >= 18);
+      {
+        let _strings: Vec<_> = (0..16).map(|_| str_pool.new()).collect();
+      }
+      assert_eq!(16, str_pool.size());
+      assert_eq!(capacity, str_pool.capacity());
+  }
+
+  #[test]
+  fn test_builder() {
+      let str_pool : Pool<String> = Pool::builder().warm(2).max(3).build();
+      assert_eq!(2, str_pool.size());
+      assert_eq!(3, str_pool.max_size());
+      {
+        let _strings: Vec<_> = (0..5).map(|_| str_pool.new()).collect();
+        assert_eq!(0, str_pool.size());
+      }
+      assert_eq!(3, str_pool.size());
+  }
+
+  #[test]
+  fn test_builder_warm_limited_by_max() {
+      let str_pool : Pool<String> = Pool::builder().warm(10).max(4).build();
+      assert_eq!(4, str_pool.size());
+      str_pool.resize(8);
+      assert_eq!(4, str_pool.size());
+  }
+
+  #[test]
+  fn test_drain_filter() {
+      let vec_pool : Pool<Vec<u8>> = Pool::with_size(0);
+      let buffers: Vec<_> = [16, 4096, 32, 8192, 64].iter()
+        .map(|&capacity| vec_pool.attach(Vec::with_capacity(capacity)))
+        .collect();
+      drop(buffers);
+      let large = vec_pool.drain_filter(|v| v.capacity() > 1024);
+      assert_eq!(2, large.len());
+      assert!(large.iter().all(|v| v.capacity() > 1024));
+      assert_eq!(3, vec_pool.size());
+      assert!(vec_pool.into_vec().iter().all(|v| v.capacity() <= 1024));
+  }
+
+  #[test]
+  fn test_with_element_recycler() {
+      let str_pool : Rc<Pool<String>> = Rc::new(Pool::with_size(0));
+      let recycled = Rc::new(Cell::new(0));
+      let vec_pool : Pool<Vec<String>> = {
+        let (str_pool, recycled) = (str_pool.clone(), recycled.clone());
+        Pool::with_element_recycler(1, move |string| {
+          recycled.set(recycled.get() + 1);
+          str_pool.attach(string);
+        })
+      };
+      {
+        let mut strings = vec_pool.new();
+        strings.push("cat".to_owned());
+        strings.push("dog".to_owned());
+        strings.push("mouse".to_owned());
+      }
+      assert_eq!(3, recycled.get());
+      assert_eq!(3, str_pool.size());
+      assert_eq!(1, vec_pool.size());
+      let mut strings = vec_pool.new();
+      assert!(strings.is_empty());
+      strings.push("bird".to_owned());
+      assert!(strings.detach_reset().is_empty());
+      assert_eq!(4, recycled.get());
+      assert_eq!(4, str_pool.size());
+  }
+
+  #[test]
+  fn test_evict_expired() {
+      let str_pool : Pool<String> = Pool::builder().warm(2).ttl(Duration::from_millis(50)).build();
+      thread::sleep(Duration::from_millis(60));
+      str_pool.new_from("cat");
+      str_pool.evict_expired();
+      assert_eq!(1, str_pool.size());
+      thread::sleep(Duration::from_millis(60));
+      str_pool.sweep();
+      assert_eq!(0, str_pool.size());
+
+      let untimed_pool : Pool<String> = Pool::with_size(2);
+      untimed_pool.evict_expired();
+      assert_eq!(2, untimed_pool.size());
+  }
+
+  #[test]
+  fn test_max_element_capacity() {
+      let vec_pool : Pool<Vec<u8>> = Pool::builder().max_element_capacity(64).build();
+      {
+        let mut small = vec_pool.new();
+        small.reserve_exact(32);
+        let mut large = vec_pool.new();
+        large.reserve_exact(1024);
+      }
+      assert_eq!(1, vec_pool.size());
+      assert!(vec_pool.new().capacity() <= 64);
+  }
+
+  #[test]
+  fn test_sweep() {
+      let vec_pool : Pool<Vec<u8>> = Pool::with_size(0);
+      let buffers: Vec<_> = [16, 2048, 32, 4096, 8192].iter().map(|&capacity| {
+        let mut buffer = vec_pool.new();
+        buffer.reserve_exact(capacity);
+        buffer
+      }).collect();
+      drop(buffers);
+      assert_eq!(5, vec_pool.size());
+      vec_pool.sweep();
+      assert_eq!(5, vec_pool.size());
+      vec_pool.set_max_element_capacity(Some(1024));
+      vec_pool.sweep();
+      assert_eq!(2, vec_pool.size());
+      assert!(vec_pool.into_vec().iter().all(|v| v.capacity() <= 1024));
+  }
+
+  #[test]
+  fn test_hash_map_max_element_capacity() {
+      let map_pool : Pool<HashMap<u32, u32>> = Pool::builder().warm(1).max_element_capacity(1024).build();
+      {
+        let mut map = map_pool.new();
+        for i in 0..100_000 {
+          map.insert(i, i);
+        }
+        assert!(map.capacity() >= 100_000);
+      }
+      assert_eq!(0, map_pool.size());
+      let map = map_pool.new();
+      assert!(map.is_empty());
+      assert!(map.capacity() <= 1024);
+  }
+
+  #[test]
+  fn test_hash_map_recycle() {
+      let map_pool : Pool<HashMap<String, u32>> = Pool::with_size(1);
+      {
+        let mut map = map_pool.new();
+        map.insert("cat".to_owned(), 1);
+      }
+      assert_eq!(1, map_pool.size());
+      assert!(map_pool.new().is_empty());
+  }
+
+  #[test]
+  fn test_with_size_and_supplier() {
+      let vec_pool : Pool<Vec<u8>> = Pool::with_size_and_supplier(2, || Vec::with_capacity(8192));
+      assert_eq!(2, vec_pool.size());
+      let warm = vec_pool.new();
+      let also_warm = vec_pool.new();
+      let fallback = vec_pool.new();
+      assert!(warm.capacity() >= 8192);
+      assert!(also_warm.capacity() >= 8192);
+      assert!(fallback.capacity() >= 8192);
+      assert_eq!(1, vec_pool.stats().misses);
+      drop((warm, also_warm, fallback));
+      vec_pool.resize(5);
+      assert!(vec_pool.into_vec().iter().all(|v| v.capacity() >= 8192));
+  }
+
+  #[test]
+  fn test_string_capacity_floor() {
+      let str_pool = Pool::<String>::with_capacity_floor(1, 64);
+      {
+        let mut rstring = str_pool.new();
+        assert!(rstring.capacity() >= 64);
+        rstring.shrink_to_fit();
+        assert_eq!(0, rstring.capacity());
+      }
+      assert!(str_pool.new().capacity() >= 64);
+      drop(str_pool.attach(String::new()));
+      assert!(str_pool.detached().capacity() >= 64);
+  }
+
+  #[test]
+  fn test_vec_capacity_floor() {
+      let vec_pool = Pool::<Vec<u8>>::with_capacity_floor(0, 128);
+      assert!(vec_pool.new().capacity() >= 128);
+      drop(vec_pool.attach(Vec::with_capacity(4)));
+      assert_eq!(2, vec_pool.size());
+      assert!(vec_pool.into_vec().iter().all(|v| v.capacity() >= 128));
+  }
+
+  #[test]
+  fn test_builder_capacity_floor() {
+      let vec_pool : Pool<Vec<u8>> = Pool::<Vec<u8>>::builder().warm(1).max(1).capacity_floor(32).build();
+      {
+        let first = vec_pool.new();
+        let second = vec_pool.new();
+        assert!(first.capacity() >= 32 && second.capacity() >= 32);
+      }
+      assert_eq!(1, vec_pool.size());
+  }
+
+  #[test]
+  fn test_resize_grow() {
+      let str_pool : Pool<String> = Pool::with_size(2);
+      let rstring = str_pool.new();
+      str_pool.resize(5);
+      assert_eq!(5, str_pool.size());
+      drop(rstring);
+      assert_eq!(6, str_pool.size());
+  }
+
+  #[test]
+  fn test_resize_shrink() {
+      let str_pool : Pool<String> = Pool::with_size(5);
+      str_pool.resize(2);
+      assert_eq!(2, str_pool.size());
+      str_pool.resize(2);
+      assert_eq!(2, str_pool.size());
+      str_pool.resize(0);
+      assert_eq!(0, str_pool.size());
+  }
+
+  #[test]
+  fn test_into_vec() {
+      let str_pool : Pool<String> = Pool::with_size(2);
+      {
+        let _rstring = str_pool.new_from("cat");
+        let _rstring = str_pool.new_from("dog");
+        let _rstring = str_pool.new_from("mouse");
+      }
+      let strings = str_pool.into_vec();
+      assert_eq!(3, strings.len());
+      assert!(strings.iter().all(|s| s.is_empty()));
+  }
+
+  #[test]
+  fn test_into_vec_with_rc_guard() {
+      let str_pool : Pool<String> = Pool::with_size(2);
+      let rstring = str_pool.new_rc_from("cat");
+      assert_eq!(1, str_pool.into_vec().len());
+      assert_eq!("cat", *rstring);
+  }
+
+  #[test]
+  fn test_retain() {
+      let vec_pool : Pool<Vec<u8>> = Pool::with_size(0);
+      {
+        let _small = vec_pool.attach(Vec::with_capacity(16));
+        let _large = vec_pool.attach(Vec::with_capacity(4096));
+        let _medium = vec_pool.attach(Vec::with_capacity(64));
+        let _huge = vec_pool.attach(Vec::with_capacity(65536));
+      }
+      assert_eq!(4, vec_pool.size());
+      vec_pool.retain(|v| v.capacity() <= 64);
+      assert_eq!(2, vec_pool.size());
+      let a = vec_pool.new();
+      let b = vec_pool.new();
+      assert!(a.capacity() <= 64);
+      assert!(b.capacity() <= 64);
+  }
+
+  #[test]
+  fn test_stats() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      assert_eq!(PoolStats::default(), str_pool.stats());
+      {
+        let _hit = str_pool.new();
+        let _miss = str_pool.new_rc();
+      }
+      let _hit = str_pool.detached();
+      let stats = str_pool.stats();
+      assert_eq!(2, stats.hits);
+      assert_eq!(1, stats.misses);
+  }
+
+  struct LeakyCounter {
+    count: u32
+  }
+
+  impl Recycleable for LeakyCounter {
+    fn new() -> LeakyCounter {
+      LeakyCounter { count: 0 }
+    }
+    fn reset(&mut self) {
+      // Forgets to zero `count`.
+    }
+    fn is_reset(&self) -> bool {
+      self.count == 0
+    }
+  }
+
+  #[test]
+  fn test_is_reset() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      assert!(str_pool.new_from("cat").detach_reset().is_reset());
+      assert!(!str_pool.new_from("cat").detach().is_reset());
+      let pool : Pool<LeakyCounter> = Pool::with_size(1);
+      assert!(pool.new().is_reset());
+  }
+
+  #[cfg(debug_assertions)]
+  #[test]
+  #[should_panic(expected = "non-reset state")]
+  fn test_is_reset_checked_on_return() {
+      let pool : Pool<LeakyCounter> = Pool::with_size(1);
+      let mut counter = pool.new();
+      counter.count += 1;
+  }
+
+  #[test]
+  fn test_reclaim() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      let other_pool : Pool<String> = Pool::with_size(0);
+      assert!(str_pool.id() != other_pool.id());
+      let (id, string) : (PoolId, String) = (str_pool.id(), str_pool.new_from("cat").detach());
+      assert_eq!(0, str_pool.size());
+      str_pool.reclaim(string, id);
+      assert_eq!(1, str_pool.size());
+      assert_eq!("", *str_pool.new());
+  }
+
+  #[cfg(debug_assertions)]
+  #[test]
+  #[should_panic(expected = "not detached from")]
+  fn test_reclaim_into_wrong_pool() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      let other_pool : Pool<String> = Pool::with_size(0);
+      let id = str_pool.id();
+      other_pool.reclaim(str_pool.new().detach(), id);
+  }
+
+  #[test]
+  fn test_checked_size() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      assert_eq!(Some(1), str_pool.checked_size());
+      str_pool.retain(|_| {
+        assert_eq!(None, str_pool.checked_size());
+        true
+      });
+      assert_eq!(Some(1), str_pool.checked_size());
+  }
+
+  thread_local! {
+    static CONSTRUCTED: ::std::cell::Cell<u32> = const { ::std::cell::Cell::new(0) };
+  }
+
+  struct MappedRegion;
+
+  impl Recycleable for MappedRegion {
+    fn new() -> MappedRegion {
+      MappedRegion::try_new().unwrap()
+    }
+    fn reset(&mut self) {}
+  }
+
+  impl TryRecycleable for MappedRegion {
+    type Error = String;
+    fn try_new() -> Result<MappedRegion, String> {
+      let count = CONSTRUCTED.with(|c| { c.set(c.get() + 1); c.get() });
+      if count == 3 {
+        Err(format!("construction {} failed", count))
+      } else {
+        Ok(MappedRegion)
+      }
+    }
+  }
+
+  #[test]
+  fn test_try_detached() {
+      let pool : Pool<MappedRegion> = Pool::with_size(0);
+      let first = pool.try_new().unwrap();
+      let second = pool.try_detached().unwrap();
+      assert_eq!(Some("construction 3 failed".to_owned()), pool.try_new().err());
+      assert_eq!(0, pool.size());
+      assert_eq!(3, pool.stats().misses);
+      drop(first);
+      drop(pool.attach(second));
+      assert_eq!(2, pool.size());
+      assert!(pool.try_detached().is_ok());
+      assert!(pool.try_new().is_ok());
+      assert_eq!(2, pool.stats().hits);
+      assert!(pool.try_new().is_ok());
+  }
+
+  #[cfg(feature = "alloc-timing")]
+  struct SlowToBuild;
+
+  #[cfg(feature = "alloc-timing")]
+  impl Recycleable for SlowToBuild {
+    fn new() -> SlowToBuild {
+      ::std::thread::sleep(::std::time::Duration::from_millis(1));
+      SlowToBuild
+    }
+    fn reset(&mut self) {}
+  }
+
+  #[cfg(feature = "alloc-timing")]
+  #[test]
+  fn test_stats_alloc_time() {
+      let pool : Pool<SlowToBuild> = Pool::with_size(1);
+      let _hit = pool.new();
+      assert_eq!(0, pool.stats().alloc_time_nanos);
+      let _miss = pool.new();
+      assert_eq!(1, pool.stats().misses);
+      assert!(pool.stats().alloc_time_nanos >= 1_000_000);
+  }
+
+  #[test]
+  fn test_buffer() {
+      let buffer_pool : Pool<Buffer<8>> = Pool::with_size(1);
+      {
+        let mut buffer = buffer_pool.new();
+        assert_eq!(8, buffer.capacity());
+        assert!(buffer.is_empty());
+        buffer.write_all(b"abc").unwrap();
+        assert_eq!(b"abc", buffer.as_slice());
+        buffer.as_mut_slice()[0] = b'x';
+        assert_eq!(b"xbc", &buffer[..]);
+        assert_eq!(5, buffer.write(b"defghijk").unwrap());
+        assert_eq!(0, buffer.write(b"l").unwrap());
+        assert!(buffer.write_all(b"l").is_err());
+        assert_eq!(b"xbcdefgh", buffer.as_slice());
+      }
+      let mut buffer = buffer_pool.new();
+      assert_eq!(0, buffer.len());
+      buffer.spare_capacity_mut()[..2].copy_from_slice(b"yz");
+      buffer.set_len(2);
+      assert_eq!(b"yz", buffer.as_slice());
+  }
+
+  #[test]
+  fn test_aligned_buffer() {
+      let buffer_pool : Pool<AlignedBuffer<64>> = Pool::with_size(1);
+      let capacity = {
+        let mut buffer = buffer_pool.new();
+        assert_eq!(0, buffer.as_ptr() as usize % 64);
+        assert!(buffer.is_empty());
+        buffer.extend_from_slice(b"abc");
+        buffer.write_all(&[7u8; 1000]).unwrap();
+        assert_eq!(1003, buffer.len());
+        assert_eq!(0, buffer.as_ptr() as usize % 64);
+        buffer.as_mut_slice()[0] = b'x';
+        assert_eq!(b"xbc", &buffer[..3]);
+        buffer.capacity()
+      };
+      let mut buffer = buffer_pool.new();
+      assert_eq!(0, buffer.len());
+      assert_eq!(capacity, buffer.capacity());
+      buffer.spare_capacity_mut()[..2].copy_from_slice(b"yz");
+      buffer.set_len(2);
+      assert_eq!(b"yz", buffer.as_slice());
+      let page : AlignedBuffer<4096> = AlignedBuffer::with_capacity(10);
+      assert_eq!(0, page.as_ptr() as usize % 4096);
+      assert!(page.capacity() >= 10);
+  }
+
+  #[test]
+  fn test_scoped_pool() {
+      let str_pool : ScopedPool<String> = Pool::with_size(2).into_scoped();
+      {
+        let mut string = str_pool.new_from("cat");
+        string.borrow_mut().push('s');
+        assert_eq!("cats", *string.borrow());
+        assert_eq!(1, str_pool.outstanding());
+      }
+      assert_eq!(0, str_pool.outstanding());
+      assert_eq!(2, str_pool.size());
+  }
+
+  #[test]
+  fn test_scoped_pool_reset_all_after_leak() {
+      let str_pool : ScopedPool<String> = ScopedPool::with_size(3);
+      ::std::mem::forget(str_pool.new_from("cat"));
+      ::std::mem::forget(str_pool.new_from("dog"));
+      let mut outliving : ScopedRecycled<String> = str_pool.new_from("mouse");
+      assert_eq!(0, str_pool.size());
+      assert_eq!(3, str_pool.outstanding());
+      str_pool.reset_all();
+      assert_eq!(3, str_pool.size());
+      assert_eq!(0, str_pool.outstanding());
+      assert!(outliving.is_reclaimed());
+      assert_eq!(Err(Error::MissingValue), outliving.try_borrow().map(|_| ()));
+      assert_eq!(Err(Error::MissingValue), outliving.try_borrow_mut().map(|_| ()));
+      drop(outliving);
+      assert_eq!(3, str_pool.size());
+      assert!(str_pool.new().borrow().is_empty());
+  }
+
+  #[test]
+  fn test_scoped_pool_reset_all_skips_borrowed() {
+      let str_pool : ScopedPool<String> = ScopedPool::with_size(2);
+      let held = str_pool.new_from("cat");
+      let leaked = str_pool.new_from("dog");
+      {
+        let string = held.borrow();
+        str_pool.reset_all();
+        assert_eq!("cat", *string);
+      }
+      assert!(leaked.is_reclaimed());
+      assert_eq!(1, str_pool.size());
+      assert_eq!(1, str_pool.outstanding());
+      ::std::mem::forget(held);
+      str_pool.reset_all();
+      assert_eq!(2, str_pool.size());
+  }
+
+  #[test]
+  #[should_panic(expected = "missing its value")]
+  fn test_scoped_pool_drop_invalidates_guards() {
+      let string = {
+        let str_pool : ScopedPool<String> = ScopedPool::with_size(1);
+        str_pool.new_from("cat")
+      };
+      string.borrow();
+  }
+
+  #[test]
+  fn test_sync_pool() {
+      let str_pool : SyncPool<String> = SyncPool::with_size(1);
+      {
+        let rstring: SyncRecycled<String> = str_pool.new_from("cat");
+        assert_eq!("cat", *rstring);
+        assert_eq!(0, str_pool.size());
+      }
+      assert_eq!(1, str_pool.size());
+      let string = str_pool.detached();
+      assert_eq!(0, str_pool.size());
+      drop(str_pool.attach(string));
+      assert_eq!(1, str_pool.size());
+  }
+
+  #[test]
+  fn test_sync_with_size_parallel() {
+      let vec_pool : SyncPool<Vec<u8>> = SyncPool::with_size_parallel(1000, 4);
+      assert_eq!(1000, vec_pool.size());
+      let uneven : SyncPool<Vec<u8>> = SyncPool::with_size_parallel(10, 3);
+      assert_eq!(10, uneven.size());
+      let no_threads : SyncPool<Vec<u8>> = SyncPool::with_size_parallel(5, 0);
+      assert_eq!(5, no_threads.size());
+  }
+
+  struct BuiltOn(thread::ThreadId);
+
+  impl Recycleable for BuiltOn {
+    fn new() -> BuiltOn {
+      BuiltOn(thread::current().id())
+    }
+    fn reset(&mut self) {}
+  }
+
+  #[test]
+  fn test_sync_with_size_parallel_clamps_threads() {
+      let oversubscribed : SyncPool<BuiltOn> = SyncPool::with_size_parallel(2, 8);
+      let threads: Vec<_> = (0..2).map(|_| oversubscribed.detached().0).collect();
+      assert_eq!(2, threads.len());
+      assert_ne!(threads[0], threads[1]);
+      assert!(threads.iter().all(|&id| id != thread::current().id()));
+      let single : SyncPool<BuiltOn> = SyncPool::with_size_parallel(1, 8);
+      // Clamped to a single thread, the values are built without spawning.
+      assert_eq!(thread::current().id(), single.detached().0);
+  }
+
+  #[test]
+  fn test_sync_new_with_releases_lock() {
+      let str_pool : SyncPool<String> = SyncPool::with_size(2);
+      let rstring = str_pool.new_with(|s| {
+        // Both of these lock the pool, which would deadlock if the lock
+        // were held across the initializer.
+        assert_eq!(1, str_pool.size());
+        let other = str_pool.new_from("dog");
+        s.push_str("cat");
+        s.push_str(&other);
+      });
+      assert_eq!("catdog", *rstring);
+      assert_eq!(1, str_pool.size());
+  }
+
+  #[test]
+  fn test_sync_recycled_send() {
+      let vec_pool : SyncPool<Vec<u8>> = SyncPool::with_size(1);
+      let mut buffer = vec_pool.new();
+      buffer.extend_from_slice(b"parent");
+      thread::scope(|scope| {
+        scope.spawn(move || {
+          buffer.extend_from_slice(b" and worker");
+          assert_eq!(b"parent and worker", &buffer[..]);
+        });
+      });
+      assert_eq!(1, vec_pool.size());
+      assert!(vec_pool.detached().is_empty());
+  }
+}
+
+#[cfg(all(test, feature = "allocator_api"))]
+mod allocator_api {
+  use std::alloc::{AllocError, Allocator, Global, Layout};
+  use std::ptr::NonNull;
+  use lifeguard::Pool;
+
+  #[derive(Clone, Copy, Default)]
+  struct Arena;
+
+  unsafe impl Allocator for Arena {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+      Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+      Global.deallocate(ptr, layout)
+    }
+  }
+
+  #[test]
+  fn test_custom_allocator_vec() {
+      let vec_pool : Pool<Vec<u8, Arena>> = Pool::with_size(1);
+      {
+        let mut rvec = vec_pool.new();
+        rvec.extend_from_slice(b"arena");
+        assert_eq!(b"arena", &rvec[..]);
+      }
+      assert_eq!(1, vec_pool.size());
+      let vec = vec_pool.detached();
+      assert!(vec.is_empty());
+      assert!(vec.capacity() >= 5);
+  }
+}
+
+#[cfg(all(test, feature = "bytes"))]
+mod bytes_interop {
+  extern crate bytes;
+  use self::bytes::{Buf, BufMut};
+  use lifeguard::Pool;
+
+  #[test]
+  fn test_buf_mut() {
+      let vec_pool : Pool<Vec<u8>> = Pool::with_size(1);
+      {
+        let mut rvec = vec_pool.new();
+        rvec.put_u8(b'c');
+        rvec.put_slice(b"at");
+        rvec.put_u16(0x2121);
+        assert_eq!(b"cat!!", &rvec[..]);
+      }
+      assert_eq!(1, vec_pool.size());
+      let mut rcvec = vec_pool.new_rc();
+      rcvec.put_u32(7);
+      assert_eq!(&[0, 0, 0, 7], &rcvec[..]);
+  }
+
+  #[test]
+  fn test_buf() {
+      let vec_pool : Pool<Vec<u8>> = Pool::with_size(1);
+      let mut rvec = vec_pool.new();
+      rvec.put_u16(0x0102);
+      rvec.put_slice(b"mice");
+      let mut buf = rvec.buf();
+      assert_eq!(6, buf.remaining());
+      assert_eq!(0x0102, buf.get_u16());
+      assert_eq!(b"mice", buf.chunk());
+      buf.advance(4);
+      assert!(!buf.has_remaining());
+      assert_eq!(6, rvec.len());
+  }
+}
+
+#[cfg(all(test, feature = "metrics"))]
+mod metrics_recorder {
+  extern crate metrics;
+  use self::metrics::{Counter, CounterFn, Gauge, GaugeFn, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit};
+  use lifeguard::Pool;
+  use std::collections::HashMap;
+  use std::panic;
+  use std::sync::{Arc, Mutex};
+  use std::thread;
+  use std::time::Duration;
+
+  type Values = Arc<Mutex<HashMap<String, f64>>>;
+
+  struct Metric {
+    key: String,
+    values: Values
+  }
+
+  impl Metric {
+    fn update<F>(&self, f: F) where F: FnOnce(&mut f64) {
+      f(self.values.lock().unwrap().entry(self.key.clone()).or_insert(0.0));
+    }
+  }
+
+  impl CounterFn for Metric {
+    fn increment(&self, value: u64) { self.update(|v| *v += value as f64) }
+    fn absolute(&self, value: u64) { self.update(|v| *v = value as f64) }
+  }
+
+  impl GaugeFn for Metric {
+    fn increment(&self, value: f64) { self.update(|v| *v += value) }
+    fn decrement(&self, value: f64) { self.update(|v| *v -= value) }
+    fn set(&self, value: f64) { self.update(|v| *v = value) }
+  }
+
+  #[derive(Default)]
+  struct TestRecorder {
+    values: Values
+  }
+
+  impl TestRecorder {
+    fn metric(&self, key: &Key) -> Arc<Metric> {
+      let labels: Vec<String> = key.labels().map(|l| format!("{}={}", l.key(), l.value())).collect();
+      Arc::new(Metric { key: format!("{}{{{}}}", key.name(), labels.join(",")), values: self.values.clone() })
+    }
+
+    fn get(&self, key: &str) -> f64 {
+      self.values.lock().unwrap().get(key).cloned().unwrap_or(0.0)
+    }
+  }
+
+  impl Recorder for TestRecorder {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter { Counter::from_arc(self.metric(key)) }
+    fn register_gauge(&self, key: &Key, _: &Metadata<'_>) -> Gauge { Gauge::from_arc(self.metric(key)) }
+    fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram { Histogram::noop() }
+  }
+
+  #[test]
+  fn test_pool_metrics() {
+      let recorder = TestRecorder::default();
+      metrics::with_local_recorder(&recorder, || {
+        let str_pool : Pool<String> = Pool::builder().warm(1).name("strings").build();
+        let first = str_pool.new();
+        let second = str_pool.new_rc();
+        assert_eq!(1.0, recorder.get("lifeguard.pool.hits{pool=strings}"));
+        assert_eq!(1.0, recorder.get("lifeguard.pool.misses{pool=strings}"));
+        assert_eq!(0.0, recorder.get("lifeguard.pool.size{pool=strings}"));
+        assert_eq!(2.0, recorder.get("lifeguard.pool.checked_out{pool=strings}"));
+        drop(first);
+        drop(second);
+        assert_eq!(2.0, recorder.get("lifeguard.pool.size{pool=strings}"));
+        assert_eq!(0.0, recorder.get("lifeguard.pool.checked_out{pool=strings}"));
+
+        let string = str_pool.new().detach();
+        assert_eq!(0.0, recorder.get("lifeguard.pool.checked_out{pool=strings}"));
+        drop(str_pool.attach(string));
+        assert_eq!(0.0, recorder.get("lifeguard.pool.checked_out{pool=strings}"));
+
+        let mut lost = str_pool.new();
+        assert_eq!(1.0, recorder.get("lifeguard.pool.checked_out{pool=strings}"));
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| lost.with_owned(|_| panic!("lost"))));
+        assert!(result.is_err());
+        drop(lost);
+        assert_eq!(0.0, recorder.get("lifeguard.pool.checked_out{pool=strings}"));
+
+        let other_pool : Pool<String> = Pool::builder().name("others").build();
+        let rebound = str_pool.new().rebind_to(&other_pool);
+        assert_eq!(0.0, recorder.get("lifeguard.pool.checked_out{pool=strings}"));
+        assert_eq!(1.0, recorder.get("lifeguard.pool.checked_out{pool=others}"));
+        drop(rebound);
+        assert_eq!(0.0, recorder.get("lifeguard.pool.checked_out{pool=others}"));
+
+        str_pool.resize(5);
+        assert_eq!(5.0, recorder.get("lifeguard.pool.size{pool=strings}"));
+        str_pool.retain(|_| false);
+        assert_eq!(0.0, recorder.get("lifeguard.pool.size{pool=strings}"));
+        str_pool.resize(2);
+        str_pool.drain_filter(|_| true);
+        assert_eq!(0.0, recorder.get("lifeguard.pool.size{pool=strings}"));
+        other_pool.into_vec();
+        assert_eq!(0.0, recorder.get("lifeguard.pool.size{pool=others}"));
+
+        let timed_pool : Pool<String> = Pool::builder().warm(2).ttl(Duration::from_millis(10)).name("timed").build();
+        assert_eq!(2.0, recorder.get("lifeguard.pool.size{pool=timed}"));
+        thread::sleep(Duration::from_millis(20));
+        timed_pool.evict_expired();
+        assert_eq!(0.0, recorder.get("lifeguard.pool.size{pool=timed}"));
+
+        let vec_pool : Pool<Vec<u8>> = Pool::with_size(0);
+        let _bytes = vec_pool.new();
+        assert_eq!(1.0, recorder.get("lifeguard.pool.misses{pool=alloc::vec::Vec<u8>}"));
+      });
+  }
+}
+
+#[cfg(test)]
+mod detach_matrix {
+  use lifeguard::Pool;
+
+  #[test]
+  fn test_string_detach_keeps_contents() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      let string = str_pool.new_from("cat").detach();
+      assert_eq!("cat", string);
+      assert_eq!(0, str_pool.size());
+  }
+
+  #[test]
+  fn test_string_detach_reset_clears_contents() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      let string = str_pool.new_from("cat").detach_reset();
+      assert_eq!("", string);
+      assert!(string.capacity() >= 3);
+      assert_eq!(0, str_pool.size());
+  }
+
+  #[test]
+  fn test_string_drop_resets_and_pools() {
+      let str_pool : Pool<String> = Pool::with_size(1);
+      {
+        let _rstring = str_pool.new_from("cat");
+      }
+      assert_eq!(1, str_pool.size());
+      let string = str_pool.detached();
+      assert_eq!("", string);
+      assert!(string.capacity() >= 3);
+  }
+
+  #[test]
+  fn test_vec_detach_keeps_contents() {
+      let vec_pool : Pool<Vec<u8>> = Pool::with_size(1);
+      let mut rvec = vec_pool.new();
+      rvec.extend_from_slice(b"mice");
+      let vec = rvec.detach();
+      assert_eq!(b"mice", &vec[..]);
+      assert_eq!(4, vec.len());
+      assert_eq!(0, vec_pool.size());
+  }
+
+  #[test]
+  fn test_vec_detach_reset_clears_contents() {
+      let vec_pool : Pool<Vec<u8>> = Pool::with_size(1);
+      let mut rvec = vec_pool.new();
+      rvec.extend_from_slice(b"mice");
+      let vec = rvec.detach_reset();
+      assert_eq!(0, vec.len());
+      assert!(vec.capacity() >= 4);
+      assert_eq!(0, vec_pool.size());
+  }
+
+  #[test]
+  fn test_vec_drop_resets_and_pools() {
+      let vec_pool : Pool<Vec<u8>> = Pool::with_size(1);
+      {
+        let mut rvec = vec_pool.new();
+        rvec.extend_from_slice(b"mice");
+      }
+      assert_eq!(1, vec_pool.size());
+      let vec = vec_pool.detached();
+      assert_eq!(0, vec.len());
+      assert!(vec.capacity() >= 4);
+  }
 }